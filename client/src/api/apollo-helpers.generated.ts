@@ -1,4 +1,14 @@
 import { FieldPolicy, FieldReadFunction, TypePolicies, TypePolicy } from '@apollo/client/cache';
+export type CardsRevealedKeySpecifier = ('game' | 'revealStage' | CardsRevealedKeySpecifier)[];
+export type CardsRevealedFieldPolicy = {
+	game?: FieldPolicy<any> | FieldReadFunction<any>,
+	revealStage?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type CardsRevealedPayloadKeySpecifier = ('game' | 'revealedBy' | CardsRevealedPayloadKeySpecifier)[];
+export type CardsRevealedPayloadFieldPolicy = {
+	game?: FieldPolicy<any> | FieldReadFunction<any>,
+	revealedBy?: FieldPolicy<any> | FieldReadFunction<any>
+};
 export type ChatMessageKeySpecifier = ('content' | 'contentType' | 'formattedContent' | 'id' | 'position' | 'roomId' | 'timestamp' | 'userId' | 'username' | ChatMessageKeySpecifier)[];
 export type ChatMessageFieldPolicy = {
 	content?: FieldPolicy<any> | FieldReadFunction<any>,
@@ -11,6 +21,10 @@ export type ChatMessageFieldPolicy = {
 	userId?: FieldPolicy<any> | FieldReadFunction<any>,
 	username?: FieldPolicy<any> | FieldReadFunction<any>
 };
+export type ChatMessageSentKeySpecifier = ('message' | ChatMessageSentKeySpecifier)[];
+export type ChatMessageSentFieldPolicy = {
+	message?: FieldPolicy<any> | FieldReadFunction<any>
+};
 export type ChatPositionKeySpecifier = ('height' | 'width' | 'x' | 'y' | ChatPositionKeySpecifier)[];
 export type ChatPositionFieldPolicy = {
 	height?: FieldPolicy<any> | FieldReadFunction<any>,
@@ -18,93 +32,327 @@ export type ChatPositionFieldPolicy = {
 	x?: FieldPolicy<any> | FieldReadFunction<any>,
 	y?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type DeckKeySpecifier = ('cards' | 'id' | DeckKeySpecifier)[];
+export type DeckKeySpecifier = ('cardDetails' | 'cards' | 'id' | 'preset' | DeckKeySpecifier)[];
 export type DeckFieldPolicy = {
+	cardDetails?: FieldPolicy<any> | FieldReadFunction<any>,
+	cards?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	preset?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type DeckCardKeySpecifier = ('color' | 'emoji' | 'label' | 'special' | 'value' | DeckCardKeySpecifier)[];
+export type DeckCardFieldPolicy = {
+	color?: FieldPolicy<any> | FieldReadFunction<any>,
+	emoji?: FieldPolicy<any> | FieldReadFunction<any>,
+	label?: FieldPolicy<any> | FieldReadFunction<any>,
+	special?: FieldPolicy<any> | FieldReadFunction<any>,
+	value?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type DeckChangedPayloadKeySpecifier = ('deck' | DeckChangedPayloadKeySpecifier)[];
+export type DeckChangedPayloadFieldPolicy = {
+	deck?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type DeckPresetInfoKeySpecifier = ('cardDetails' | 'cards' | 'name' | 'preset' | DeckPresetInfoKeySpecifier)[];
+export type DeckPresetInfoFieldPolicy = {
+	cardDetails?: FieldPolicy<any> | FieldReadFunction<any>,
 	cards?: FieldPolicy<any> | FieldReadFunction<any>,
-	id?: FieldPolicy<any> | FieldReadFunction<any>
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	preset?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type DeckUpdatedKeySpecifier = ('deck' | DeckUpdatedKeySpecifier)[];
+export type DeckUpdatedFieldPolicy = {
+	deck?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type GameKeySpecifier = ('id' | 'table' | GameKeySpecifier)[];
+export type FinalEstimateSetPayloadKeySpecifier = ('card' | 'setBy' | 'storyId' | FinalEstimateSetPayloadKeySpecifier)[];
+export type FinalEstimateSetPayloadFieldPolicy = {
+	card?: FieldPolicy<any> | FieldReadFunction<any>,
+	setBy?: FieldPolicy<any> | FieldReadFunction<any>,
+	storyId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type GameKeySpecifier = ('id' | 'statistics' | 'table' | GameKeySpecifier)[];
 export type GameFieldPolicy = {
 	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	statistics?: FieldPolicy<any> | FieldReadFunction<any>,
 	table?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type MutationRootKeySpecifier = ('banUser' | 'cancelRevealCountdown' | 'createRoom' | 'createUser' | 'editUser' | 'joinRoom' | 'kickUser' | 'logout' | 'markChatSeen' | 'pickCard' | 'renameRoom' | 'resetGame' | 'sendChatMessage' | 'setRoomOwner' | 'showCards' | 'startRevealCountdown' | 'toggleConfirmNewGame' | 'toggleCountdownOption' | 'unbanUser' | 'updateDeck' | MutationRootKeySpecifier)[];
+export type GameResetPayloadKeySpecifier = ('gameId' | 'resetBy' | GameResetPayloadKeySpecifier)[];
+export type GameResetPayloadFieldPolicy = {
+	gameId?: FieldPolicy<any> | FieldReadFunction<any>,
+	resetBy?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MutationRootKeySpecifier = ('addStory' | 'banUser' | 'cancelRevealCountdown' | 'createRoom' | 'createUser' | 'deleteStory' | 'editUser' | 'importStories' | 'joinRoom' | 'kickUser' | 'logout' | 'markChatSeen' | 'pickCard' | 'renameRoom' | 'reorderStories' | 'resetGame' | 'selectStory' | 'sendChatMessage' | 'setFinalEstimate' | 'setRoomOwner' | 'setRoomPasscode' | 'setRoundTitle' | 'setSpectator' | 'showCards' | 'startRevealCountdown' | 'toggleConfirmNewGame' | 'toggleCountdownOption' | 'unbanUser' | 'updateDeck' | 'updateRoomSettings' | 'updateStory' | MutationRootKeySpecifier)[];
 export type MutationRootFieldPolicy = {
+	addStory?: FieldPolicy<any> | FieldReadFunction<any>,
 	banUser?: FieldPolicy<any> | FieldReadFunction<any>,
 	cancelRevealCountdown?: FieldPolicy<any> | FieldReadFunction<any>,
 	createRoom?: FieldPolicy<any> | FieldReadFunction<any>,
 	createUser?: FieldPolicy<any> | FieldReadFunction<any>,
+	deleteStory?: FieldPolicy<any> | FieldReadFunction<any>,
 	editUser?: FieldPolicy<any> | FieldReadFunction<any>,
+	importStories?: FieldPolicy<any> | FieldReadFunction<any>,
 	joinRoom?: FieldPolicy<any> | FieldReadFunction<any>,
 	kickUser?: FieldPolicy<any> | FieldReadFunction<any>,
 	logout?: FieldPolicy<any> | FieldReadFunction<any>,
 	markChatSeen?: FieldPolicy<any> | FieldReadFunction<any>,
 	pickCard?: FieldPolicy<any> | FieldReadFunction<any>,
 	renameRoom?: FieldPolicy<any> | FieldReadFunction<any>,
+	reorderStories?: FieldPolicy<any> | FieldReadFunction<any>,
 	resetGame?: FieldPolicy<any> | FieldReadFunction<any>,
+	selectStory?: FieldPolicy<any> | FieldReadFunction<any>,
 	sendChatMessage?: FieldPolicy<any> | FieldReadFunction<any>,
+	setFinalEstimate?: FieldPolicy<any> | FieldReadFunction<any>,
 	setRoomOwner?: FieldPolicy<any> | FieldReadFunction<any>,
+	setRoomPasscode?: FieldPolicy<any> | FieldReadFunction<any>,
+	setRoundTitle?: FieldPolicy<any> | FieldReadFunction<any>,
+	setSpectator?: FieldPolicy<any> | FieldReadFunction<any>,
 	showCards?: FieldPolicy<any> | FieldReadFunction<any>,
 	startRevealCountdown?: FieldPolicy<any> | FieldReadFunction<any>,
 	toggleConfirmNewGame?: FieldPolicy<any> | FieldReadFunction<any>,
 	toggleCountdownOption?: FieldPolicy<any> | FieldReadFunction<any>,
 	unbanUser?: FieldPolicy<any> | FieldReadFunction<any>,
-	updateDeck?: FieldPolicy<any> | FieldReadFunction<any>
+	updateDeck?: FieldPolicy<any> | FieldReadFunction<any>,
+	updateRoomSettings?: FieldPolicy<any> | FieldReadFunction<any>,
+	updateStory?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type QueryRootKeySpecifier = ('roomById' | 'rooms' | 'userRooms' | QueryRootKeySpecifier)[];
+export type OwnerChangedPayloadKeySpecifier = ('ownerId' | 'previousOwnerId' | OwnerChangedPayloadKeySpecifier)[];
+export type OwnerChangedPayloadFieldPolicy = {
+	ownerId?: FieldPolicy<any> | FieldReadFunction<any>,
+	previousOwnerId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type QueryRootKeySpecifier = ('decks' | 'roomById' | 'roomPreview' | 'roomSync' | 'rooms' | 'userRooms' | QueryRootKeySpecifier)[];
 export type QueryRootFieldPolicy = {
+	decks?: FieldPolicy<any> | FieldReadFunction<any>,
 	roomById?: FieldPolicy<any> | FieldReadFunction<any>,
+	roomPreview?: FieldPolicy<any> | FieldReadFunction<any>,
+	roomSync?: FieldPolicy<any> | FieldReadFunction<any>,
 	rooms?: FieldPolicy<any> | FieldReadFunction<any>,
 	userRooms?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type RoomKeySpecifier = ('bannedUsers' | 'chatHistory' | 'confirmNewGame' | 'countdownEnabled' | 'countdownValue' | 'deck' | 'game' | 'hasUnreadChat' | 'id' | 'isGameOver' | 'name' | 'revealStage' | 'roomOwnerId' | 'users' | RoomKeySpecifier)[];
+export type RevealStageChangedKeySpecifier = ('countdownValue' | 'revealStage' | RevealStageChangedKeySpecifier)[];
+export type RevealStageChangedFieldPolicy = {
+	countdownValue?: FieldPolicy<any> | FieldReadFunction<any>,
+	revealStage?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomKeySpecifier = ('autoResetSeconds' | 'autoReveal' | 'bannedUsers' | 'chatHistory' | 'confirmNewGame' | 'countdownEnabled' | 'countdownSeconds' | 'countdownValue' | 'currentStoryId' | 'deck' | 'game' | 'hasPasscode' | 'hasUnreadChat' | 'id' | 'isGameOver' | 'name' | 'revealStage' | 'roomOwnerId' | 'roundCount' | 'roundTitle' | 'rounds' | 'stories' | 'users' | 'version' | 'voteLock' | RoomKeySpecifier)[];
 export type RoomFieldPolicy = {
+	autoResetSeconds?: FieldPolicy<any> | FieldReadFunction<any>,
+	autoReveal?: FieldPolicy<any> | FieldReadFunction<any>,
 	bannedUsers?: FieldPolicy<any> | FieldReadFunction<any>,
 	chatHistory?: FieldPolicy<any> | FieldReadFunction<any>,
 	confirmNewGame?: FieldPolicy<any> | FieldReadFunction<any>,
 	countdownEnabled?: FieldPolicy<any> | FieldReadFunction<any>,
+	countdownSeconds?: FieldPolicy<any> | FieldReadFunction<any>,
 	countdownValue?: FieldPolicy<any> | FieldReadFunction<any>,
+	currentStoryId?: FieldPolicy<any> | FieldReadFunction<any>,
 	deck?: FieldPolicy<any> | FieldReadFunction<any>,
 	game?: FieldPolicy<any> | FieldReadFunction<any>,
+	hasPasscode?: FieldPolicy<any> | FieldReadFunction<any>,
 	hasUnreadChat?: FieldPolicy<any> | FieldReadFunction<any>,
 	id?: FieldPolicy<any> | FieldReadFunction<any>,
 	isGameOver?: FieldPolicy<any> | FieldReadFunction<any>,
 	name?: FieldPolicy<any> | FieldReadFunction<any>,
 	revealStage?: FieldPolicy<any> | FieldReadFunction<any>,
 	roomOwnerId?: FieldPolicy<any> | FieldReadFunction<any>,
-	users?: FieldPolicy<any> | FieldReadFunction<any>
+	roundCount?: FieldPolicy<any> | FieldReadFunction<any>,
+	roundTitle?: FieldPolicy<any> | FieldReadFunction<any>,
+	rounds?: FieldPolicy<any> | FieldReadFunction<any>,
+	stories?: FieldPolicy<any> | FieldReadFunction<any>,
+	users?: FieldPolicy<any> | FieldReadFunction<any>,
+	version?: FieldPolicy<any> | FieldReadFunction<any>,
+	voteLock?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type RoomEventKeySpecifier = ('eventType' | 'room' | 'roomId' | 'targetUserId' | RoomEventKeySpecifier)[];
+export type RoomChangeKeySpecifier = ('delta' | 'roomId' | 'version' | RoomChangeKeySpecifier)[];
+export type RoomChangeFieldPolicy = {
+	delta?: FieldPolicy<any> | FieldReadFunction<any>,
+	roomId?: FieldPolicy<any> | FieldReadFunction<any>,
+	version?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomEventKeySpecifier = ('eventType' | 'payload' | 'room' | 'roomId' | 'targetUserId' | RoomEventKeySpecifier)[];
 export type RoomEventFieldPolicy = {
 	eventType?: FieldPolicy<any> | FieldReadFunction<any>,
+	payload?: FieldPolicy<any> | FieldReadFunction<any>,
 	room?: FieldPolicy<any> | FieldReadFunction<any>,
 	roomId?: FieldPolicy<any> | FieldReadFunction<any>,
 	targetUserId?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type SubscriptionRootKeySpecifier = ('room' | 'roomChat' | 'roomEvents' | SubscriptionRootKeySpecifier)[];
+export type RoomExpiredPayloadKeySpecifier = ('roomName' | RoomExpiredPayloadKeySpecifier)[];
+export type RoomExpiredPayloadFieldPolicy = {
+	roomName?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomPreviewKeySpecifier = ('exists' | 'hasPasscode' | 'name' | RoomPreviewKeySpecifier)[];
+export type RoomPreviewFieldPolicy = {
+	exists?: FieldPolicy<any> | FieldReadFunction<any>,
+	hasPasscode?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomRenamedPayloadKeySpecifier = ('name' | RoomRenamedPayloadKeySpecifier)[];
+export type RoomRenamedPayloadFieldPolicy = {
+	name?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomReplacedKeySpecifier = ('room' | RoomReplacedKeySpecifier)[];
+export type RoomReplacedFieldPolicy = {
+	room?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomSummaryKeySpecifier = ('hasPasscode' | 'id' | 'isGameOver' | 'lastActive' | 'name' | 'roomOwnerId' | 'userCount' | RoomSummaryKeySpecifier)[];
+export type RoomSummaryFieldPolicy = {
+	hasPasscode?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	isGameOver?: FieldPolicy<any> | FieldReadFunction<any>,
+	lastActive?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	roomOwnerId?: FieldPolicy<any> | FieldReadFunction<any>,
+	userCount?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoomSyncKeySpecifier = ('room' | 'version' | RoomSyncKeySpecifier)[];
+export type RoomSyncFieldPolicy = {
+	room?: FieldPolicy<any> | FieldReadFunction<any>,
+	version?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type RoundKeySpecifier = ('finalEstimate' | 'finalEstimateAt' | 'finalEstimateBy' | 'gameId' | 'number' | 'revealedAt' | 'revealedBy' | 'statistics' | 'storyId' | 'storyTitle' | 'votes' | RoundKeySpecifier)[];
+export type RoundFieldPolicy = {
+	finalEstimate?: FieldPolicy<any> | FieldReadFunction<any>,
+	finalEstimateAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	finalEstimateBy?: FieldPolicy<any> | FieldReadFunction<any>,
+	gameId?: FieldPolicy<any> | FieldReadFunction<any>,
+	number?: FieldPolicy<any> | FieldReadFunction<any>,
+	revealedAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	revealedBy?: FieldPolicy<any> | FieldReadFunction<any>,
+	statistics?: FieldPolicy<any> | FieldReadFunction<any>,
+	storyId?: FieldPolicy<any> | FieldReadFunction<any>,
+	storyTitle?: FieldPolicy<any> | FieldReadFunction<any>,
+	votes?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type SettingsChangedKeySpecifier = ('autoResetSeconds' | 'autoReveal' | 'confirmNewGame' | 'countdownEnabled' | 'countdownSeconds' | 'hasPasscode' | 'name' | 'voteLock' | SettingsChangedKeySpecifier)[];
+export type SettingsChangedFieldPolicy = {
+	autoResetSeconds?: FieldPolicy<any> | FieldReadFunction<any>,
+	autoReveal?: FieldPolicy<any> | FieldReadFunction<any>,
+	confirmNewGame?: FieldPolicy<any> | FieldReadFunction<any>,
+	countdownEnabled?: FieldPolicy<any> | FieldReadFunction<any>,
+	countdownSeconds?: FieldPolicy<any> | FieldReadFunction<any>,
+	hasPasscode?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	voteLock?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type StoryKeySpecifier = ('description' | 'externalKey' | 'finalEstimate' | 'id' | 'status' | 'title' | StoryKeySpecifier)[];
+export type StoryFieldPolicy = {
+	description?: FieldPolicy<any> | FieldReadFunction<any>,
+	externalKey?: FieldPolicy<any> | FieldReadFunction<any>,
+	finalEstimate?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	status?: FieldPolicy<any> | FieldReadFunction<any>,
+	title?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type StoryImportErrorKeySpecifier = ('message' | 'row' | StoryImportErrorKeySpecifier)[];
+export type StoryImportErrorFieldPolicy = {
+	message?: FieldPolicy<any> | FieldReadFunction<any>,
+	row?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type StoryImportResultKeySpecifier = ('errors' | 'importedCount' | 'room' | StoryImportResultKeySpecifier)[];
+export type StoryImportResultFieldPolicy = {
+	errors?: FieldPolicy<any> | FieldReadFunction<any>,
+	importedCount?: FieldPolicy<any> | FieldReadFunction<any>,
+	room?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type SubscriptionRootKeySpecifier = ('room' | 'roomChanges' | 'roomChat' | 'roomEvents' | SubscriptionRootKeySpecifier)[];
 export type SubscriptionRootFieldPolicy = {
 	room?: FieldPolicy<any> | FieldReadFunction<any>,
+	roomChanges?: FieldPolicy<any> | FieldReadFunction<any>,
 	roomChat?: FieldPolicy<any> | FieldReadFunction<any>,
 	roomEvents?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type UserKeySpecifier = ('id' | 'lastCardPicked' | 'lastCardValue' | 'lastSeenChatMessageId' | 'username' | UserKeySpecifier)[];
+export type UserKeySpecifier = ('id' | 'isSpectator' | 'lastCardPicked' | 'lastCardValue' | 'lastSeen' | 'lastSeenChatMessageId' | 'online' | 'username' | UserKeySpecifier)[];
 export type UserFieldPolicy = {
 	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	isSpectator?: FieldPolicy<any> | FieldReadFunction<any>,
 	lastCardPicked?: FieldPolicy<any> | FieldReadFunction<any>,
 	lastCardValue?: FieldPolicy<any> | FieldReadFunction<any>,
+	lastSeen?: FieldPolicy<any> | FieldReadFunction<any>,
 	lastSeenChatMessageId?: FieldPolicy<any> | FieldReadFunction<any>,
+	online?: FieldPolicy<any> | FieldReadFunction<any>,
 	username?: FieldPolicy<any> | FieldReadFunction<any>
 };
+export type UserBannedPayloadKeySpecifier = ('bannedBy' | 'userId' | UserBannedPayloadKeySpecifier)[];
+export type UserBannedPayloadFieldPolicy = {
+	bannedBy?: FieldPolicy<any> | FieldReadFunction<any>,
+	userId?: FieldPolicy<any> | FieldReadFunction<any>
+};
 export type UserCardKeySpecifier = ('card' | 'userId' | UserCardKeySpecifier)[];
 export type UserCardFieldPolicy = {
 	card?: FieldPolicy<any> | FieldReadFunction<any>,
 	userId?: FieldPolicy<any> | FieldReadFunction<any>
 };
+export type UserJoinedKeySpecifier = ('user' | UserJoinedKeySpecifier)[];
+export type UserJoinedFieldPolicy = {
+	user?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserKickedPayloadKeySpecifier = ('kickedBy' | 'userId' | UserKickedPayloadKeySpecifier)[];
+export type UserKickedPayloadFieldPolicy = {
+	kickedBy?: FieldPolicy<any> | FieldReadFunction<any>,
+	userId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserLeftKeySpecifier = ('banned' | 'userId' | UserLeftKeySpecifier)[];
+export type UserLeftFieldPolicy = {
+	banned?: FieldPolicy<any> | FieldReadFunction<any>,
+	userId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserOfflinePayloadKeySpecifier = ('lastSeen' | 'userId' | UserOfflinePayloadKeySpecifier)[];
+export type UserOfflinePayloadFieldPolicy = {
+	lastSeen?: FieldPolicy<any> | FieldReadFunction<any>,
+	userId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserOnlinePayloadKeySpecifier = ('userId' | UserOnlinePayloadKeySpecifier)[];
+export type UserOnlinePayloadFieldPolicy = {
+	userId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserSessionKeySpecifier = ('token' | 'user' | UserSessionKeySpecifier)[];
+export type UserSessionFieldPolicy = {
+	token?: FieldPolicy<any> | FieldReadFunction<any>,
+	user?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserTimedOutPayloadKeySpecifier = ('userId' | UserTimedOutPayloadKeySpecifier)[];
+export type UserTimedOutPayloadFieldPolicy = {
+	userId?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type UserUpdatedKeySpecifier = ('user' | UserUpdatedKeySpecifier)[];
+export type UserUpdatedFieldPolicy = {
+	user?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type VoteCastKeySpecifier = ('card' | 'userId' | 'voted' | VoteCastKeySpecifier)[];
+export type VoteCastFieldPolicy = {
+	card?: FieldPolicy<any> | FieldReadFunction<any>,
+	userId?: FieldPolicy<any> | FieldReadFunction<any>,
+	voted?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type VoteStatisticsKeySpecifier = ('consensus' | 'max' | 'mean' | 'median' | 'min' | 'mode' | 'nearestCard' | 'nonNumericCount' | 'numericCount' | 'stdDev' | 'voteCount' | VoteStatisticsKeySpecifier)[];
+export type VoteStatisticsFieldPolicy = {
+	consensus?: FieldPolicy<any> | FieldReadFunction<any>,
+	max?: FieldPolicy<any> | FieldReadFunction<any>,
+	mean?: FieldPolicy<any> | FieldReadFunction<any>,
+	median?: FieldPolicy<any> | FieldReadFunction<any>,
+	min?: FieldPolicy<any> | FieldReadFunction<any>,
+	mode?: FieldPolicy<any> | FieldReadFunction<any>,
+	nearestCard?: FieldPolicy<any> | FieldReadFunction<any>,
+	nonNumericCount?: FieldPolicy<any> | FieldReadFunction<any>,
+	numericCount?: FieldPolicy<any> | FieldReadFunction<any>,
+	stdDev?: FieldPolicy<any> | FieldReadFunction<any>,
+	voteCount?: FieldPolicy<any> | FieldReadFunction<any>
+};
 export type StrictTypedTypePolicies = {
+	CardsRevealed?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | CardsRevealedKeySpecifier | (() => undefined | CardsRevealedKeySpecifier),
+		fields?: CardsRevealedFieldPolicy,
+	},
+	CardsRevealedPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | CardsRevealedPayloadKeySpecifier | (() => undefined | CardsRevealedPayloadKeySpecifier),
+		fields?: CardsRevealedPayloadFieldPolicy,
+	},
 	ChatMessage?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | ChatMessageKeySpecifier | (() => undefined | ChatMessageKeySpecifier),
 		fields?: ChatMessageFieldPolicy,
 	},
+	ChatMessageSent?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | ChatMessageSentKeySpecifier | (() => undefined | ChatMessageSentKeySpecifier),
+		fields?: ChatMessageSentFieldPolicy,
+	},
 	ChatPosition?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | ChatPositionKeySpecifier | (() => undefined | ChatPositionKeySpecifier),
 		fields?: ChatPositionFieldPolicy,
@@ -113,26 +361,106 @@ export type StrictTypedTypePolicies = {
 		keyFields?: false | DeckKeySpecifier | (() => undefined | DeckKeySpecifier),
 		fields?: DeckFieldPolicy,
 	},
+	DeckCard?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | DeckCardKeySpecifier | (() => undefined | DeckCardKeySpecifier),
+		fields?: DeckCardFieldPolicy,
+	},
+	DeckChangedPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | DeckChangedPayloadKeySpecifier | (() => undefined | DeckChangedPayloadKeySpecifier),
+		fields?: DeckChangedPayloadFieldPolicy,
+	},
+	DeckPresetInfo?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | DeckPresetInfoKeySpecifier | (() => undefined | DeckPresetInfoKeySpecifier),
+		fields?: DeckPresetInfoFieldPolicy,
+	},
+	DeckUpdated?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | DeckUpdatedKeySpecifier | (() => undefined | DeckUpdatedKeySpecifier),
+		fields?: DeckUpdatedFieldPolicy,
+	},
+	FinalEstimateSetPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | FinalEstimateSetPayloadKeySpecifier | (() => undefined | FinalEstimateSetPayloadKeySpecifier),
+		fields?: FinalEstimateSetPayloadFieldPolicy,
+	},
 	Game?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | GameKeySpecifier | (() => undefined | GameKeySpecifier),
 		fields?: GameFieldPolicy,
 	},
+	GameResetPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | GameResetPayloadKeySpecifier | (() => undefined | GameResetPayloadKeySpecifier),
+		fields?: GameResetPayloadFieldPolicy,
+	},
 	MutationRoot?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | MutationRootKeySpecifier | (() => undefined | MutationRootKeySpecifier),
 		fields?: MutationRootFieldPolicy,
 	},
+	OwnerChangedPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | OwnerChangedPayloadKeySpecifier | (() => undefined | OwnerChangedPayloadKeySpecifier),
+		fields?: OwnerChangedPayloadFieldPolicy,
+	},
 	QueryRoot?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | QueryRootKeySpecifier | (() => undefined | QueryRootKeySpecifier),
 		fields?: QueryRootFieldPolicy,
 	},
+	RevealStageChanged?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RevealStageChangedKeySpecifier | (() => undefined | RevealStageChangedKeySpecifier),
+		fields?: RevealStageChangedFieldPolicy,
+	},
 	Room?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | RoomKeySpecifier | (() => undefined | RoomKeySpecifier),
 		fields?: RoomFieldPolicy,
 	},
+	RoomChange?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomChangeKeySpecifier | (() => undefined | RoomChangeKeySpecifier),
+		fields?: RoomChangeFieldPolicy,
+	},
 	RoomEvent?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | RoomEventKeySpecifier | (() => undefined | RoomEventKeySpecifier),
 		fields?: RoomEventFieldPolicy,
 	},
+	RoomExpiredPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomExpiredPayloadKeySpecifier | (() => undefined | RoomExpiredPayloadKeySpecifier),
+		fields?: RoomExpiredPayloadFieldPolicy,
+	},
+	RoomPreview?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomPreviewKeySpecifier | (() => undefined | RoomPreviewKeySpecifier),
+		fields?: RoomPreviewFieldPolicy,
+	},
+	RoomRenamedPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomRenamedPayloadKeySpecifier | (() => undefined | RoomRenamedPayloadKeySpecifier),
+		fields?: RoomRenamedPayloadFieldPolicy,
+	},
+	RoomReplaced?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomReplacedKeySpecifier | (() => undefined | RoomReplacedKeySpecifier),
+		fields?: RoomReplacedFieldPolicy,
+	},
+	RoomSummary?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomSummaryKeySpecifier | (() => undefined | RoomSummaryKeySpecifier),
+		fields?: RoomSummaryFieldPolicy,
+	},
+	RoomSync?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoomSyncKeySpecifier | (() => undefined | RoomSyncKeySpecifier),
+		fields?: RoomSyncFieldPolicy,
+	},
+	Round?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | RoundKeySpecifier | (() => undefined | RoundKeySpecifier),
+		fields?: RoundFieldPolicy,
+	},
+	SettingsChanged?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | SettingsChangedKeySpecifier | (() => undefined | SettingsChangedKeySpecifier),
+		fields?: SettingsChangedFieldPolicy,
+	},
+	Story?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | StoryKeySpecifier | (() => undefined | StoryKeySpecifier),
+		fields?: StoryFieldPolicy,
+	},
+	StoryImportError?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | StoryImportErrorKeySpecifier | (() => undefined | StoryImportErrorKeySpecifier),
+		fields?: StoryImportErrorFieldPolicy,
+	},
+	StoryImportResult?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | StoryImportResultKeySpecifier | (() => undefined | StoryImportResultKeySpecifier),
+		fields?: StoryImportResultFieldPolicy,
+	},
 	SubscriptionRoot?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | SubscriptionRootKeySpecifier | (() => undefined | SubscriptionRootKeySpecifier),
 		fields?: SubscriptionRootFieldPolicy,
@@ -141,9 +469,53 @@ export type StrictTypedTypePolicies = {
 		keyFields?: false | UserKeySpecifier | (() => undefined | UserKeySpecifier),
 		fields?: UserFieldPolicy,
 	},
+	UserBannedPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserBannedPayloadKeySpecifier | (() => undefined | UserBannedPayloadKeySpecifier),
+		fields?: UserBannedPayloadFieldPolicy,
+	},
 	UserCard?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | UserCardKeySpecifier | (() => undefined | UserCardKeySpecifier),
 		fields?: UserCardFieldPolicy,
+	},
+	UserJoined?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserJoinedKeySpecifier | (() => undefined | UserJoinedKeySpecifier),
+		fields?: UserJoinedFieldPolicy,
+	},
+	UserKickedPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserKickedPayloadKeySpecifier | (() => undefined | UserKickedPayloadKeySpecifier),
+		fields?: UserKickedPayloadFieldPolicy,
+	},
+	UserLeft?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserLeftKeySpecifier | (() => undefined | UserLeftKeySpecifier),
+		fields?: UserLeftFieldPolicy,
+	},
+	UserOfflinePayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserOfflinePayloadKeySpecifier | (() => undefined | UserOfflinePayloadKeySpecifier),
+		fields?: UserOfflinePayloadFieldPolicy,
+	},
+	UserOnlinePayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserOnlinePayloadKeySpecifier | (() => undefined | UserOnlinePayloadKeySpecifier),
+		fields?: UserOnlinePayloadFieldPolicy,
+	},
+	UserSession?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserSessionKeySpecifier | (() => undefined | UserSessionKeySpecifier),
+		fields?: UserSessionFieldPolicy,
+	},
+	UserTimedOutPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserTimedOutPayloadKeySpecifier | (() => undefined | UserTimedOutPayloadKeySpecifier),
+		fields?: UserTimedOutPayloadFieldPolicy,
+	},
+	UserUpdated?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UserUpdatedKeySpecifier | (() => undefined | UserUpdatedKeySpecifier),
+		fields?: UserUpdatedFieldPolicy,
+	},
+	VoteCast?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | VoteCastKeySpecifier | (() => undefined | VoteCastKeySpecifier),
+		fields?: VoteCastFieldPolicy,
+	},
+	VoteStatistics?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | VoteStatisticsKeySpecifier | (() => undefined | VoteStatisticsKeySpecifier),
+		fields?: VoteStatisticsFieldPolicy,
 	}
 };
 export type TypedTypePolicies = StrictTypedTypePolicies & TypePolicies;
\ No newline at end of file
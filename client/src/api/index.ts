@@ -2,20 +2,48 @@ import {TypedTypePolicies} from "@/api/apollo-helpers.generated.ts";
 
 export * from "./operations.generated";
 
-import { ApolloClient, HttpLink, InMemoryCache, split } from "@apollo/client";
+import { ApolloClient, from, HttpLink, InMemoryCache, split } from "@apollo/client";
+import { setContext } from "@apollo/client/link/context";
+import { onError } from "@apollo/client/link/error";
 import { GraphQLWsLink } from "@apollo/client/link/subscriptions";
 import { getMainDefinition } from "@apollo/client/utilities";
 import { createClient } from "graphql-ws";
 
 import { GRAPHQL_ENDPOINT, GRAPHQL_WS_ENDPOINT } from "@/settings";
+import { expireSession, getTokenFromLocalStorage } from "@/utils/auth";
 
 const httpLink = new HttpLink({
   uri: GRAPHQL_ENDPOINT,
 });
 
+// The session token from createUser identifies the caller on every request.
+const authLink = setContext((_, { headers }) => {
+  const token = getTokenFromLocalStorage();
+
+  return {
+    headers: {
+      ...headers,
+      ...(token ? { Authorization: `Bearer ${token}` } : {}),
+    },
+  };
+});
+
+// Users saved before sessions existed (or whose token the server no longer
+// accepts) have to be created again.
+const errorLink = onError(({ graphQLErrors }) => {
+  if (graphQLErrors?.some((error) => error.extensions?.code === "UNAUTHENTICATED")) {
+    expireSession();
+  }
+});
+
 const wsLink = new GraphQLWsLink(
   createClient({
     url: GRAPHQL_WS_ENDPOINT,
+    // Read on every (re)connect so a fresh login is picked up.
+    connectionParams: () => {
+      const token = getTokenFromLocalStorage();
+      return token ? { authToken: token } : {};
+    },
   }),
 );
 
@@ -28,7 +56,7 @@ const splitLink = split(
     );
   },
   wsLink,
-  httpLink,
+  authLink.concat(httpLink),
 );
 
 const typePolicies: TypedTypePolicies = {
@@ -41,6 +69,6 @@ const typePolicies: TypedTypePolicies = {
 };
 
 export const client = new ApolloClient({
-  link: splitLink,
+  link: from([errorLink, splitLink]),
   cache: new InMemoryCache({ typePolicies }),
 });
@@ -3,37 +3,43 @@ import * as Types from '../types/types.generated';
 import { gql } from '@apollo/client';
 import * as Apollo from '@apollo/client';
 const defaultOptions = {} as const;
-export type UserFragmentFragment = { __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null };
+export type UserFragmentFragment = { __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null };
 
-export type DeckFragmentFragment = { __typename?: 'Deck', id: string, cards: Array<string> };
+export type DeckCardFragmentFragment = { __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null };
+
+export type DeckFragmentFragment = { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> };
 
 export type UserCardFragmentFragment = { __typename?: 'UserCard', userId: string, card?: string | null };
 
 export type GameFragmentFragment = { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> };
 
+export type StoryFragmentFragment = { __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus };
+
+export type RoundFragmentFragment = { __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> };
+
 export type ChatPositionFragmentFragment = { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number };
 
 export type ChatMessageFragmentFragment = { __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null };
 
-export type RoomFragmentFragment = { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> };
+export type RoomFragmentFragment = { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> };
 
-export type RoomEventFragmentFragment = { __typename?: 'RoomEvent', roomId: string, eventType: string, targetUserId?: string | null, room: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type RoomEventFragmentFragment = { __typename?: 'RoomEvent', roomId: string, eventType: Types.RoomEventType, targetUserId?: string | null, room: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type CreateRoomMutationVariables = Types.Exact<{
   roomId?: Types.InputMaybe<Types.Scalars['UUID']['input']>;
   name?: Types.InputMaybe<Types.Scalars['String']['input']>;
-  cards: Array<Types.Scalars['String']['input']> | Types.Scalars['String']['input'];
+  cards?: Types.InputMaybe<Array<Types.Scalars['String']['input']> | Types.Scalars['String']['input']>;
 }>;
 
 
-export type CreateRoomMutation = { __typename?: 'MutationRoot', createRoom: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type CreateRoomMutation = { __typename?: 'MutationRoot', createRoom: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type CreateUserMutationVariables = Types.Exact<{
   username: Types.Scalars['String']['input'];
 }>;
 
 
-export type CreateUserMutation = { __typename?: 'MutationRoot', createUser: { __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null } };
+export type CreateUserMutation = { __typename?: 'MutationRoot', createUser: { __typename?: 'UserSession', token: string, user: { __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null } } };
 
 export type JoinRoomMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -42,7 +48,7 @@ export type JoinRoomMutationVariables = Types.Exact<{
 }>;
 
 
-export type JoinRoomMutation = { __typename?: 'MutationRoot', joinRoom: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type JoinRoomMutation = { __typename?: 'MutationRoot', joinRoom: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type UpdateDeckMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -50,7 +56,7 @@ export type UpdateDeckMutationVariables = Types.Exact<{
 }>;
 
 
-export type UpdateDeckMutation = { __typename?: 'MutationRoot', updateDeck: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type UpdateDeckMutation = { __typename?: 'MutationRoot', updateDeck: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type RenameRoomMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -58,7 +64,7 @@ export type RenameRoomMutationVariables = Types.Exact<{
 }>;
 
 
-export type RenameRoomMutation = { __typename?: 'MutationRoot', renameRoom: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type RenameRoomMutation = { __typename?: 'MutationRoot', renameRoom: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type ToggleCountdownOptionMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -66,23 +72,21 @@ export type ToggleCountdownOptionMutationVariables = Types.Exact<{
 }>;
 
 
-export type ToggleCountdownOptionMutation = { __typename?: 'MutationRoot', toggleCountdownOption: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type ToggleCountdownOptionMutation = { __typename?: 'MutationRoot', toggleCountdownOption: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type StartRevealCountdownMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
-  userId?: Types.InputMaybe<Types.Scalars['UUID']['input']>;
 }>;
 
 
-export type StartRevealCountdownMutation = { __typename?: 'MutationRoot', startRevealCountdown: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type StartRevealCountdownMutation = { __typename?: 'MutationRoot', startRevealCountdown: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type CancelRevealCountdownMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
-  userId?: Types.InputMaybe<Types.Scalars['UUID']['input']>;
 }>;
 
 
-export type CancelRevealCountdownMutation = { __typename?: 'MutationRoot', cancelRevealCountdown: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type CancelRevealCountdownMutation = { __typename?: 'MutationRoot', cancelRevealCountdown: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type SetRoomOwnerMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -90,45 +94,41 @@ export type SetRoomOwnerMutationVariables = Types.Exact<{
 }>;
 
 
-export type SetRoomOwnerMutation = { __typename?: 'MutationRoot', setRoomOwner: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type SetRoomOwnerMutation = { __typename?: 'MutationRoot', setRoomOwner: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type EditUserMutationVariables = Types.Exact<{
-  userId: Types.Scalars['UUID']['input'];
   username: Types.Scalars['String']['input'];
 }>;
 
 
-export type EditUserMutation = { __typename?: 'MutationRoot', editUser: { __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null } };
+export type EditUserMutation = { __typename?: 'MutationRoot', editUser: { __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null } };
 
-export type LogoutMutationVariables = Types.Exact<{
-  userId: Types.Scalars['UUID']['input'];
-}>;
+export type LogoutMutationVariables = Types.Exact<{ [key: string]: never; }>;
 
 
 export type LogoutMutation = { __typename?: 'MutationRoot', logout: boolean };
 
 export type PickCardMutationVariables = Types.Exact<{
-  userId: Types.Scalars['UUID']['input'];
   roomId: Types.Scalars['UUID']['input'];
   card: Types.Scalars['String']['input'];
 }>;
 
 
-export type PickCardMutation = { __typename?: 'MutationRoot', pickCard: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type PickCardMutation = { __typename?: 'MutationRoot', pickCard: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type ShowCardsMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
 }>;
 
 
-export type ShowCardsMutation = { __typename?: 'MutationRoot', showCards: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type ShowCardsMutation = { __typename?: 'MutationRoot', showCards: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type ResetGameMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
 }>;
 
 
-export type ResetGameMutation = { __typename?: 'MutationRoot', resetGame: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type ResetGameMutation = { __typename?: 'MutationRoot', resetGame: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type KickUserMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -136,7 +136,7 @@ export type KickUserMutationVariables = Types.Exact<{
 }>;
 
 
-export type KickUserMutation = { __typename?: 'MutationRoot', kickUser: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type KickUserMutation = { __typename?: 'MutationRoot', kickUser: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type BanUserMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -144,7 +144,7 @@ export type BanUserMutationVariables = Types.Exact<{
 }>;
 
 
-export type BanUserMutation = { __typename?: 'MutationRoot', banUser: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type BanUserMutation = { __typename?: 'MutationRoot', banUser: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type UnbanUserMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -152,7 +152,7 @@ export type UnbanUserMutationVariables = Types.Exact<{
 }>;
 
 
-export type UnbanUserMutation = { __typename?: 'MutationRoot', unbanUser: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type UnbanUserMutation = { __typename?: 'MutationRoot', unbanUser: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type ToggleConfirmNewGameMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -160,12 +160,10 @@ export type ToggleConfirmNewGameMutationVariables = Types.Exact<{
 }>;
 
 
-export type ToggleConfirmNewGameMutation = { __typename?: 'MutationRoot', toggleConfirmNewGame: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type ToggleConfirmNewGameMutation = { __typename?: 'MutationRoot', toggleConfirmNewGame: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type SendChatMessageMutationVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
-  userId: Types.Scalars['UUID']['input'];
-  username: Types.Scalars['String']['input'];
   content: Types.Scalars['String']['input'];
   formattedContent?: Types.InputMaybe<Types.Scalars['String']['input']>;
   contentType: Types.Scalars['String']['input'];
@@ -188,7 +186,7 @@ export type RoomSubscriptionVariables = Types.Exact<{
 }>;
 
 
-export type RoomSubscription = { __typename?: 'SubscriptionRoot', room: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } };
+export type RoomSubscription = { __typename?: 'SubscriptionRoot', room: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } };
 
 export type RoomChatSubscriptionVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -202,7 +200,7 @@ export type RoomEventsSubscriptionVariables = Types.Exact<{
 }>;
 
 
-export type RoomEventsSubscription = { __typename?: 'SubscriptionRoot', roomEvents: { __typename?: 'RoomEvent', roomId: string, eventType: string, targetUserId?: string | null, room: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } } };
+export type RoomEventsSubscription = { __typename?: 'SubscriptionRoot', roomEvents: { __typename?: 'RoomEvent', roomId: string, eventType: Types.RoomEventType, targetUserId?: string | null, room: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } } };
 
 export type RoomUnreadSubscriptionVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -217,7 +215,14 @@ export type GetRoomQueryVariables = Types.Exact<{
 }>;
 
 
-export type GetRoomQuery = { __typename?: 'QueryRoot', roomById?: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: string | null, countdownValue?: number | null, confirmNewGame: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null }>, deck: { __typename?: 'Deck', id: string, cards: Array<string> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }> } | null };
+export type GetRoomQuery = { __typename?: 'QueryRoot', roomById?: { __typename?: 'Room', id: string, name?: string | null, isGameOver: boolean, roomOwnerId?: string | null, bannedUsers: Array<string>, countdownEnabled: boolean, revealStage?: Types.RevealStage | null, countdownValue?: number | null, confirmNewGame: boolean, countdownSeconds: number, autoReveal: boolean, autoResetSeconds?: number | null, voteLock: Types.VoteLockPolicy, roundTitle?: string | null, currentStoryId?: string | null, version: number, roundCount: number, hasPasscode: boolean, users: Array<{ __typename?: 'User', id: string, username: string, lastCardPicked?: string | null, lastCardValue?: number | null, lastSeenChatMessageId?: string | null, isSpectator: boolean, online: boolean, lastSeen?: string | null }>, deck: { __typename?: 'Deck', id: string, preset?: Types.DeckPreset | null, cards: Array<string>, cardDetails: Array<{ __typename?: 'DeckCard', label: string, value?: number | null, color?: string | null, emoji?: string | null, special?: Types.SpecialCard | null }> }, game: { __typename?: 'Game', id: string, table: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }, chatHistory: Array<{ __typename?: 'ChatMessage', id: string, roomId: string, userId: string, username: string, content: string, formattedContent?: string | null, contentType: string, timestamp: string, position?: { __typename?: 'ChatPosition', x: number, y: number, width: number, height: number } | null }>, stories: Array<{ __typename?: 'Story', id: string, title: string, description?: string | null, externalKey?: string | null, finalEstimate?: string | null, status: Types.StoryStatus }>, rounds: Array<{ __typename?: 'Round', number: number, gameId: string, storyId?: string | null, storyTitle?: string | null, revealedAt: string, revealedBy?: string | null, finalEstimate?: string | null, finalEstimateAt?: string | null, finalEstimateBy?: string | null, votes: Array<{ __typename?: 'UserCard', userId: string, card?: string | null }> }> } | null };
+
+export type GetRoomPreviewQueryVariables = Types.Exact<{
+  roomId: Types.Scalars['UUID']['input'];
+}>;
+
+
+export type GetRoomPreviewQuery = { __typename?: 'QueryRoot', roomPreview: { __typename?: 'RoomPreview', exists: boolean, name?: string | null, hasPasscode: boolean } };
 
 export type GetRoomUnreadQueryVariables = Types.Exact<{
   roomId: Types.Scalars['UUID']['input'];
@@ -234,14 +239,30 @@ export const UserFragmentFragmentDoc = gql`
   lastCardPicked
   lastCardValue
   lastSeenChatMessageId
+  isSpectator
+  online
+  lastSeen
+}
+    `;
+export const DeckCardFragmentFragmentDoc = gql`
+    fragment DeckCardFragment on DeckCard {
+  label
+  value
+  color
+  emoji
+  special
 }
     `;
 export const DeckFragmentFragmentDoc = gql`
     fragment DeckFragment on Deck {
   id
+  preset
   cards
+  cardDetails {
+    ...DeckCardFragment
+  }
 }
-    `;
+    ${DeckCardFragmentFragmentDoc}`;
 export const UserCardFragmentFragmentDoc = gql`
     fragment UserCardFragment on UserCard {
   userId
@@ -256,6 +277,32 @@ export const GameFragmentFragmentDoc = gql`
   }
 }
     ${UserCardFragmentFragmentDoc}`;
+export const StoryFragmentFragmentDoc = gql`
+    fragment StoryFragment on Story {
+  id
+  title
+  description
+  externalKey
+  finalEstimate
+  status
+}
+    `;
+export const RoundFragmentFragmentDoc = gql`
+    fragment RoundFragment on Round {
+  number
+  gameId
+  storyId
+  storyTitle
+  votes {
+    ...UserCardFragment
+  }
+  revealedAt
+  revealedBy
+  finalEstimate
+  finalEstimateAt
+  finalEstimateBy
+}
+    ${UserCardFragmentFragmentDoc}`;
 export const ChatPositionFragmentFragmentDoc = gql`
     fragment ChatPositionFragment on ChatPosition {
   x
@@ -299,14 +346,31 @@ export const RoomFragmentFragmentDoc = gql`
   revealStage
   countdownValue
   confirmNewGame
+  countdownSeconds
+  autoReveal
+  autoResetSeconds
+  voteLock
   chatHistory {
     ...ChatMessageFragment
   }
+  roundTitle
+  stories {
+    ...StoryFragment
+  }
+  currentStoryId
+  version
+  rounds {
+    ...RoundFragment
+  }
+  roundCount
+  hasPasscode
 }
     ${UserFragmentFragmentDoc}
 ${DeckFragmentFragmentDoc}
 ${GameFragmentFragmentDoc}
-${ChatMessageFragmentFragmentDoc}`;
+${ChatMessageFragmentFragmentDoc}
+${StoryFragmentFragmentDoc}
+${RoundFragmentFragmentDoc}`;
 export const RoomEventFragmentFragmentDoc = gql`
     fragment RoomEventFragment on RoomEvent {
   roomId
@@ -318,7 +382,7 @@ export const RoomEventFragmentFragmentDoc = gql`
 }
     ${RoomFragmentFragmentDoc}`;
 export const CreateRoomDocument = gql`
-    mutation CreateRoom($roomId: UUID, $name: String, $cards: [String!]!) {
+    mutation CreateRoom($roomId: UUID, $name: String, $cards: [String!]) {
   createRoom(roomId: $roomId, name: $name, cards: $cards) {
     ...RoomFragment
   }
@@ -355,7 +419,10 @@ export type CreateRoomMutationOptions = Apollo.BaseMutationOptions<CreateRoomMut
 export const CreateUserDocument = gql`
     mutation CreateUser($username: String!) {
   createUser(username: $username) {
-    ...UserFragment
+    token
+    user {
+      ...UserFragment
+    }
   }
 }
     ${UserFragmentFragmentDoc}`;
@@ -523,8 +590,8 @@ export type ToggleCountdownOptionMutationHookResult = ReturnType<typeof useToggl
 export type ToggleCountdownOptionMutationResult = Apollo.MutationResult<ToggleCountdownOptionMutation>;
 export type ToggleCountdownOptionMutationOptions = Apollo.BaseMutationOptions<ToggleCountdownOptionMutation, ToggleCountdownOptionMutationVariables>;
 export const StartRevealCountdownDocument = gql`
-    mutation StartRevealCountdown($roomId: UUID!, $userId: UUID) {
-  startRevealCountdown(roomId: $roomId, userId: $userId) {
+    mutation StartRevealCountdown($roomId: UUID!) {
+  startRevealCountdown(roomId: $roomId) {
     ...RoomFragment
   }
 }
@@ -545,7 +612,6 @@ export type StartRevealCountdownMutationFn = Apollo.MutationFunction<StartReveal
  * const [startRevealCountdownMutation, { data, loading, error }] = useStartRevealCountdownMutation({
  *   variables: {
  *      roomId: // value for 'roomId'
- *      userId: // value for 'userId'
  *   },
  * });
  */
@@ -557,8 +623,8 @@ export type StartRevealCountdownMutationHookResult = ReturnType<typeof useStartR
 export type StartRevealCountdownMutationResult = Apollo.MutationResult<StartRevealCountdownMutation>;
 export type StartRevealCountdownMutationOptions = Apollo.BaseMutationOptions<StartRevealCountdownMutation, StartRevealCountdownMutationVariables>;
 export const CancelRevealCountdownDocument = gql`
-    mutation CancelRevealCountdown($roomId: UUID!, $userId: UUID) {
-  cancelRevealCountdown(roomId: $roomId, userId: $userId) {
+    mutation CancelRevealCountdown($roomId: UUID!) {
+  cancelRevealCountdown(roomId: $roomId) {
     ...RoomFragment
   }
 }
@@ -579,7 +645,6 @@ export type CancelRevealCountdownMutationFn = Apollo.MutationFunction<CancelReve
  * const [cancelRevealCountdownMutation, { data, loading, error }] = useCancelRevealCountdownMutation({
  *   variables: {
  *      roomId: // value for 'roomId'
- *      userId: // value for 'userId'
  *   },
  * });
  */
@@ -625,8 +690,8 @@ export type SetRoomOwnerMutationHookResult = ReturnType<typeof useSetRoomOwnerMu
 export type SetRoomOwnerMutationResult = Apollo.MutationResult<SetRoomOwnerMutation>;
 export type SetRoomOwnerMutationOptions = Apollo.BaseMutationOptions<SetRoomOwnerMutation, SetRoomOwnerMutationVariables>;
 export const EditUserDocument = gql`
-    mutation EditUser($userId: UUID!, $username: String!) {
-  editUser(userId: $userId, username: $username) {
+    mutation EditUser($username: String!) {
+  editUser(username: $username) {
     ...UserFragment
   }
 }
@@ -646,7 +711,6 @@ export type EditUserMutationFn = Apollo.MutationFunction<EditUserMutation, EditU
  * @example
  * const [editUserMutation, { data, loading, error }] = useEditUserMutation({
  *   variables: {
- *      userId: // value for 'userId'
  *      username: // value for 'username'
  *   },
  * });
@@ -659,8 +723,8 @@ export type EditUserMutationHookResult = ReturnType<typeof useEditUserMutation>;
 export type EditUserMutationResult = Apollo.MutationResult<EditUserMutation>;
 export type EditUserMutationOptions = Apollo.BaseMutationOptions<EditUserMutation, EditUserMutationVariables>;
 export const LogoutDocument = gql`
-    mutation Logout($userId: UUID!) {
-  logout(userId: $userId)
+    mutation Logout {
+  logout
 }
     `;
 export type LogoutMutationFn = Apollo.MutationFunction<LogoutMutation, LogoutMutationVariables>;
@@ -678,7 +742,6 @@ export type LogoutMutationFn = Apollo.MutationFunction<LogoutMutation, LogoutMut
  * @example
  * const [logoutMutation, { data, loading, error }] = useLogoutMutation({
  *   variables: {
- *      userId: // value for 'userId'
  *   },
  * });
  */
@@ -690,8 +753,8 @@ export type LogoutMutationHookResult = ReturnType<typeof useLogoutMutation>;
 export type LogoutMutationResult = Apollo.MutationResult<LogoutMutation>;
 export type LogoutMutationOptions = Apollo.BaseMutationOptions<LogoutMutation, LogoutMutationVariables>;
 export const PickCardDocument = gql`
-    mutation PickCard($userId: UUID!, $roomId: UUID!, $card: String!) {
-  pickCard(userId: $userId, roomId: $roomId, card: $card) {
+    mutation PickCard($roomId: UUID!, $card: String!) {
+  pickCard(roomId: $roomId, card: $card) {
     ...RoomFragment
   }
 }
@@ -711,7 +774,6 @@ export type PickCardMutationFn = Apollo.MutationFunction<PickCardMutation, PickC
  * @example
  * const [pickCardMutation, { data, loading, error }] = usePickCardMutation({
  *   variables: {
- *      userId: // value for 'userId'
  *      roomId: // value for 'roomId'
  *      card: // value for 'card'
  *   },
@@ -927,9 +989,9 @@ export type ToggleConfirmNewGameMutationHookResult = ReturnType<typeof useToggle
 export type ToggleConfirmNewGameMutationResult = Apollo.MutationResult<ToggleConfirmNewGameMutation>;
 export type ToggleConfirmNewGameMutationOptions = Apollo.BaseMutationOptions<ToggleConfirmNewGameMutation, ToggleConfirmNewGameMutationVariables>;
 export const SendChatMessageDocument = gql`
-    mutation SendChatMessage($roomId: UUID!, $userId: UUID!, $username: String!, $content: String!, $formattedContent: String, $contentType: String!, $position: ChatPositionInput) {
+    mutation SendChatMessage($roomId: UUID!, $content: String!, $formattedContent: String, $contentType: String!, $position: ChatPositionInput) {
   sendChatMessage(
-    input: {roomId: $roomId, userId: $userId, username: $username, content: $content, formattedContent: $formattedContent, contentType: $contentType, position: $position}
+    input: {roomId: $roomId, content: $content, formattedContent: $formattedContent, contentType: $contentType, position: $position}
   ) {
     ...ChatMessageFragment
   }
@@ -951,8 +1013,6 @@ export type SendChatMessageMutationFn = Apollo.MutationFunction<SendChatMessageM
  * const [sendChatMessageMutation, { data, loading, error }] = useSendChatMessageMutation({
  *   variables: {
  *      roomId: // value for 'roomId'
- *      userId: // value for 'userId'
- *      username: // value for 'username'
  *      content: // value for 'content'
  *      formattedContent: // value for 'formattedContent'
  *      contentType: // value for 'contentType'
@@ -969,7 +1029,7 @@ export type SendChatMessageMutationResult = Apollo.MutationResult<SendChatMessag
 export type SendChatMessageMutationOptions = Apollo.BaseMutationOptions<SendChatMessageMutation, SendChatMessageMutationVariables>;
 export const MarkChatSeenDocument = gql`
     mutation MarkChatSeen($roomId: UUID!, $userId: UUID!) {
-  markChatSeen(roomId: $roomId, userId: $userId) {
+  markChatSeen(roomId: $roomId) {
     id
     hasUnreadChat(userId: $userId)
   }
@@ -1159,6 +1219,43 @@ export function useGetRoomLazyQuery(baseOptions?: Apollo.LazyQueryHookOptions<Ge
 export type GetRoomQueryHookResult = ReturnType<typeof useGetRoomQuery>;
 export type GetRoomLazyQueryHookResult = ReturnType<typeof useGetRoomLazyQuery>;
 export type GetRoomQueryResult = Apollo.QueryResult<GetRoomQuery, GetRoomQueryVariables>;
+export const GetRoomPreviewDocument = gql`
+    query GetRoomPreview($roomId: UUID!) {
+  roomPreview(roomId: $roomId) {
+    exists
+    name
+    hasPasscode
+  }
+}
+    `;
+
+/**
+ * __useGetRoomPreviewQuery__
+ *
+ * To run a query within a React component, call `useGetRoomPreviewQuery` and pass it any options that fit your needs.
+ * When your component renders, `useGetRoomPreviewQuery` returns an object from Apollo Client that contains loading, error, and data properties
+ * you can use to render your UI.
+ *
+ * @param baseOptions options that will be passed into the query, supported options are listed on: https://www.apollographql.com/docs/react/api/react-hooks/#options;
+ *
+ * @example
+ * const { data, loading, error } = useGetRoomPreviewQuery({
+ *   variables: {
+ *      roomId: // value for 'roomId'
+ *   },
+ * });
+ */
+export function useGetRoomPreviewQuery(baseOptions: Apollo.QueryHookOptions<GetRoomPreviewQuery, GetRoomPreviewQueryVariables>) {
+        const options = {...defaultOptions, ...baseOptions}
+        return Apollo.useQuery<GetRoomPreviewQuery, GetRoomPreviewQueryVariables>(GetRoomPreviewDocument, options);
+      }
+export function useGetRoomPreviewLazyQuery(baseOptions?: Apollo.LazyQueryHookOptions<GetRoomPreviewQuery, GetRoomPreviewQueryVariables>) {
+          const options = {...defaultOptions, ...baseOptions}
+          return Apollo.useLazyQuery<GetRoomPreviewQuery, GetRoomPreviewQueryVariables>(GetRoomPreviewDocument, options);
+        }
+export type GetRoomPreviewQueryHookResult = ReturnType<typeof useGetRoomPreviewQuery>;
+export type GetRoomPreviewLazyQueryHookResult = ReturnType<typeof useGetRoomPreviewLazyQuery>;
+export type GetRoomPreviewQueryResult = Apollo.QueryResult<GetRoomPreviewQuery, GetRoomPreviewQueryVariables>;
 export const GetRoomUnreadDocument = gql`
     query GetRoomUnread($roomId: UUID!, $userId: UUID!) {
   roomById(roomId: $roomId) {
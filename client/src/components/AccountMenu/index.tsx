@@ -102,7 +102,7 @@ export const AccountMenu: FC<AccountMenuProps> = ({ room, onOpenChange, highligh
       });
     }
 
-    await logoutMutation({ variables: { userId: user.id } });
+    await logoutMutation();
 
     localStorage.removeItem("Room");
   }
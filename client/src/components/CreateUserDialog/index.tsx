@@ -73,23 +73,22 @@ export const CreateUserDialog: FC<CreateUserDialogProps> = ({
           DEFAULT_CARDS.findIndex((card) => card === b)
       );
 
-      login?.({
-        id: data.createUser.id,
-        username: data.createUser.username
-      });
+      const { user, token } = data.createUser;
+
+      login?.(user, token);
 
       setOpen(false);
 
       if (users.length < 1) {
         onJoin(
-          data.createUser,
+          user,
           sortedSelectedCards,
-          data.createUser.id,
+          user.id,
           roomName !== "" ? roomName : null
         );
       } else {
         onJoin(
-          data.createUser,
+          user,
           roomData.deck.cards,
           roomData.roomOwnerId,
           roomData.name
@@ -65,7 +65,7 @@ export function Deck({ roomId, isGameOver: isGameOverProp, cards }: DeckProps) {
 
     try {
       await pickCardMutation({
-        variables: { userId: authUser.id, roomId, card: cardToSend },
+        variables: { roomId, card: cardToSend },
       });
       const result = await refetch({ roomId });
       const refreshedRoom = result?.data?.roomById ?? null;
@@ -48,10 +48,7 @@ export const EditUserDialog: FC<EditUserDialogProps> = ({ open, setOpen }) => {
   const [editUserMutation, { loading }] = useEditUserMutation({
     onCompleted: (data) => {
       // mutation only runs when the username has actually changed, so this is a true update
-      login?.({
-        id: data.editUser.id,
-        username: data.editUser.username,
-      });
+      login?.(data.editUser);
       setOpen(false);
 
       toast({
@@ -92,7 +89,6 @@ export const EditUserDialog: FC<EditUserDialogProps> = ({ open, setOpen }) => {
     if (user) {
       await editUserMutation({
         variables: {
-          userId: user.id,
           username: trimmed,
         },
       });
@@ -424,8 +424,6 @@ export function Player({
       await sendChatMessage({
         variables: {
           roomId,
-          userId: currentUserId,
-          username: user.username,
           content: plain,
           formattedContent: formatted,
           contentType: "html",
@@ -191,7 +191,7 @@ export const Table: FC<TableProps> = ({
     try {
       if (room.countdownEnabled) {
         await startRevealCountdown({
-          variables: { roomId: room.id, userId: currentUserId },
+          variables: { roomId: room.id },
         });
       } else {
         await showCardsMutation({
@@ -441,7 +441,7 @@ export const Table: FC<TableProps> = ({
                 isRoomOwner={currentIsRoomOwner}
                 onCancel={() =>
                   cancelRevealCountdownMutation({
-                    variables: { roomId: room.id, userId: currentUserId },
+                    variables: { roomId: room.id },
                   })
                 }
               />
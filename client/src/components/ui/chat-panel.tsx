@@ -274,12 +274,16 @@ export const ChatPanel: React.FC<{
 
     try {
       const position = getCardRect(currentUserId);
-      const variables: any = {
-        ...newMessage,
-        position,
-      };
-
-      await sendChatMessage({ variables });
+      // The server stamps the sender from the session.
+      await sendChatMessage({
+        variables: {
+          roomId,
+          content: plain,
+          formattedContent: formatted,
+          contentType: "html",
+          position,
+        },
+      });
     } catch (err) {
       console.error("Failed to send chat:", err);
       toast({
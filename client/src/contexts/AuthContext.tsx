@@ -9,15 +9,19 @@ import {
 
 import { User } from "@/types";
 import {
+  getTokenFromLocalStorage,
   getUserFromLocalStorage,
+  onSessionExpired,
   removeRoomFromLocalStorage,
+  removeTokenFromLocalStorage,
   removeUserFromLocalStorage,
+  setTokenToLocalStorage,
   setUserToLocalStorage
 } from "@/utils";
 
 export interface AuthContextValues {
   user: User | null;
-  login?: (user: User) => void;
+  login?: (user: User, token?: string) => void;
   logout?: () => void;
 }
 
@@ -35,16 +39,23 @@ export function AuthProvider({ children }: Props) {
   const [user, setUser] = useState<User | null>(null);
 
   useEffect(() => {
+    // A user saved before sessions existed has no token; start over.
+    if (!getTokenFromLocalStorage()) removeUserFromLocalStorage();
     setUser(getUserFromLocalStorage());
+
+    return onSessionExpired(() => setUser(null));
   }, []);
 
-  const handleLogin = useCallback((user: User) => {
+  const handleLogin = useCallback((user: User, token?: string) => {
+    // Profile edits keep the session token issued by createUser.
+    if (token) setTokenToLocalStorage(token);
     setUserToLocalStorage(user);
     setUser(user);
   }, []);
 
   const handleLogout = useCallback(() => {
     removeUserFromLocalStorage();
+    removeTokenFromLocalStorage();
     removeRoomFromLocalStorage();
     sessionStorage.removeItem("HAS_JOINED_ROOM");
     setUser(null);
@@ -1,7 +1,7 @@
 import { useNavigate } from "@tanstack/react-router";
 import {FC, useEffect, useMemo, useRef, useState} from "react";
 
-import {useCreateRoomMutation, useGetRoomQuery} from "@/api";
+import {useCreateRoomMutation, useGetRoomPreviewQuery} from "@/api";
 import { ModeToggle } from "@/components/mode-toggle";
 import { Button } from "@/components/ui/button";
 import {
@@ -215,7 +215,7 @@ export const HomePage: FC = () => {
   }, []);
 
   // ===== Verify room exists on server =====
-  const { data, loading: roomCheckLoading, error: roomError } = useGetRoomQuery({
+  const { data, loading: roomCheckLoading, error: roomError } = useGetRoomPreviewQuery({
     variables: { roomId: storedRoom?.RoomID ?? "" },
     skip: !storedRoom?.RoomID,
     fetchPolicy: "network-only"
@@ -232,10 +232,9 @@ export const HomePage: FC = () => {
       return;
     }
 
-    // Room exists if the query returned data
-    if (data?.roomById?.id) {
+    if (data?.roomPreview.exists) {
       setValidRoom(true);
-    } else if (!roomCheckLoading && !data?.roomById?.id) {
+    } else if (!roomCheckLoading) {
       setValidRoom(false);
     }
   }, [data, roomError, roomCheckLoading, storedRoom?.RoomID, loading]);
@@ -2,6 +2,7 @@ import { useParams, useNavigate } from "@tanstack/react-router";
 import { useEffect, useRef, useState } from "react";
 
 import {
+  useGetRoomPreviewQuery,
   useGetRoomQuery,
   useJoinRoomMutation,
   useLogoutMutation,
@@ -18,7 +19,7 @@ import { RoomOptionsDialog } from "@/components/RoomOptionsDialog";
 import { VoteDistributionChart } from "@/components/vote-distribution-chart";
 import { useAuth } from "@/contexts";
 import { useToast } from "@/hooks/use-toast";
-import { User } from "@/types";
+import { RoomEventType, User } from "@/types";
 import { validate as validateUUID } from "uuid";
 import {ResultsTag} from "@/components/ui/results-tag.tsx";
 import {StarrySky} from "@/components/StarrySky";
@@ -47,18 +48,16 @@ export function RoomPage() {
     setChatVisible(true);
   };
 
-  const { data: subscriptionData, error: roomSubscriptionError } =
-    useRoomSubscription({
-      variables: { roomId }
-    });
-
-  const { data: roomEventsData, error: roomEventsError } =
-    useRoomEventsSubscription({
-      variables: { roomId }
-    });
+  // Whether the room exists, which `roomById` won't say to non-members.
+  const { data: previewData } = useGetRoomPreviewQuery({
+    variables: { roomId },
+    fetchPolicy: "network-only"
+  });
+  const roomExists = previewData?.roomPreview.exists;
 
   const { data: roomData, error: roomError } = useGetRoomQuery({
     variables: { roomId },
+    skip: !user,
     fetchPolicy: "network-only"
   });
 
@@ -88,7 +87,7 @@ export function RoomPage() {
     },
     onError: (error) => {
       const msg = error.message?.toLowerCase() ?? "";
-      const roomName = roomData?.roomById?.name ?? "this room";
+      const roomName = previewData?.roomPreview.name ?? "this room";
       const memoryKey = `kickban-${roomId}-${user?.id ?? "unknown"}`;
 
       // Ignore harmless rejoin or missing-room errors
@@ -123,6 +122,19 @@ export function RoomPage() {
     }
   });
 
+  // Room subscriptions are for members only, so they wait for the join.
+  const { data: subscriptionData, error: roomSubscriptionError } =
+    useRoomSubscription({
+      variables: { roomId },
+      skip: !joinRoomData
+    });
+
+  const { data: roomEventsData, error: roomEventsError } =
+    useRoomEventsSubscription({
+      variables: { roomId },
+      skip: !joinRoomData
+    });
+
   // --- Kick / Ban detection ---
   useEffect(() => {
     if (!roomEventsData?.roomEvents || !user) return;
@@ -130,7 +142,7 @@ export function RoomPage() {
     const event = roomEventsData.roomEvents;
     if (event.targetUserId !== user.id) return; // ignore events for other users
 
-    if (event.eventType === "USER_KICKED") {
+    if (event.eventType === RoomEventType.UserKicked) {
       // don't double-toast if you triggered it yourself
       if (!localStorage.getItem("KICKED")) {
         toast({
@@ -144,9 +156,7 @@ export function RoomPage() {
       // Force full auth logout: backend + local
       try {
         if (user?.id) {
-          logoutMutation({
-            variables: { userId: user.id }
-          }).then(() => {
+          logoutMutation().then(() => {
             logout?.();
 
             setOpenCreateUserDialog(true);
@@ -157,7 +167,7 @@ export function RoomPage() {
       }
     }
 
-    if (event.eventType === "USER_BANNED") {
+    if (event.eventType === RoomEventType.UserBanned) {
       toast({
         title: "You were banned",
         description: "You have been banned from the room.",
@@ -172,7 +182,7 @@ export function RoomPage() {
 
   // --- Initial join logic ---
   useEffect(() => {
-    if (user === undefined || !roomData) return;
+    if (user === undefined || !roomExists || (user && !roomData)) return;
 
     const isNewRoom = sessionStorage.getItem("NEW_ROOM_CREATED") === "true";
     if (isNewRoom) {
@@ -181,7 +191,7 @@ export function RoomPage() {
       return;
     }
 
-    if (!user && roomData.roomById && roomData.roomById.users.length >= 0) {
+    if (!user) {
       setOpenCreateUserDialog(true);
       return;
     }
@@ -211,7 +221,7 @@ export function RoomPage() {
         roomOwner = roomStorage.RoomOwner;
       }
 
-      if (!roomStorage && roomData.roomById) {
+      if (!roomStorage && roomData?.roomById) {
         const storageData = {
           RoomID: roomData.roomById.id,
           Cards: roomData.roomById.deck.cards,
@@ -225,7 +235,6 @@ export function RoomPage() {
         variables: {
           roomId,
           user: {
-            id: user.id,
             username: user.username,
             roomName: roomName && roomName.trim().length > 0 ? roomName : undefined
           },
@@ -255,7 +264,7 @@ export function RoomPage() {
 
       isJoinRoomCalledRef.current = true;
     }
-  }, [roomData, user, joinRoomMutation, roomId]);
+  }, [roomExists, roomData, user, joinRoomMutation, roomId]);
 
   // --- Join helper ---
   async function handleJoinRoomMutation(
@@ -288,31 +297,24 @@ export function RoomPage() {
         }
       }
 
-      if (selectedCards) {
-        await updateDeck({
-          variables: {
-            roomId,
-            cards: selectedCards.map(String)
-          }
-        });
-      }
-
-      await joinRoomMutation({
+      // Join first: that is how the creator claims the room, and only the
+      // owner may set its deck.
+      const { data } = await joinRoomMutation({
         variables: {
           roomId: roomId,
           user: {
-            id: user.id,
             username: user.username,
             roomName: roomName ?? undefined
-          }
+          },
+          roomOwnerId: roomOwnerId ?? undefined
         }
       });
 
-      if (roomOwnerId) {
-        await setRoomOwner({
+      if (selectedCards && data?.joinRoom.roomOwnerId === user.id) {
+        await updateDeck({
           variables: {
-            roomId: roomId,
-            userId: roomOwnerId
+            roomId,
+            cards: selectedCards.map(String)
           }
         });
       }
@@ -377,8 +379,7 @@ export function RoomPage() {
     }
   }, [room, user]);
 
-  const isMissingRoom =
-    roomData && roomData.roomById === null && !joinRoomData && !subscriptionData;
+  const isMissingRoom = roomExists === false && !joinRoomData && !subscriptionData;
 
   // --- Redirects ---
   useEffect(() => {
@@ -393,7 +394,7 @@ export function RoomPage() {
   }, [roomId, navigate]);
 
   useEffect(() => {
-    if (roomData && roomData.roomById === null) {
+    if (roomExists === false) {
       redirectingRef.current = true;
       const timeout = setTimeout(() => {
         if (!joinRoomData && !subscriptionData) {
@@ -402,7 +403,7 @@ export function RoomPage() {
       }, 400);
       return () => clearTimeout(timeout);
     }
-  }, [roomData, joinRoomData, subscriptionData, roomId, navigate]);
+  }, [roomExists, joinRoomData, subscriptionData, roomId, navigate]);
 
   // --- Error handlers ---
   useEffect(() => {
@@ -1,3 +1,4 @@
 export * from "./apiUrls";
 
 export const USER_KEY = "user";
+export const TOKEN_KEY = "token";
@@ -16,6 +16,28 @@ export type Scalars = {
   UUID: { input: string; output: string; }
 };
 
+export type AddStoryInput = {
+  description?: InputMaybe<Scalars['String']['input']>;
+  externalKey?: InputMaybe<Scalars['String']['input']>;
+  roomId: Scalars['UUID']['input'];
+  title: Scalars['String']['input'];
+};
+
+/** The round was revealed: `game` holds every card and the statistics. */
+export type CardsRevealed = {
+  __typename?: 'CardsRevealed';
+  game: Game;
+  revealStage?: Maybe<RevealStage>;
+};
+
+export type CardsRevealedPayload = {
+  __typename?: 'CardsRevealedPayload';
+  /** Every card on the table, with the statistics. */
+  game: Game;
+  /** `None` for an automatic reveal. */
+  revealedBy?: Maybe<Scalars['UUID']['output']>;
+};
+
 /** A chat message within a room. */
 export type ChatMessage = {
   __typename?: 'ChatMessage';
@@ -33,6 +55,11 @@ export type ChatMessage = {
   username: Scalars['String']['output'];
 };
 
+export type ChatMessageSent = {
+  __typename?: 'ChatMessageSent';
+  message: ChatMessage;
+};
+
 export type ChatPosition = {
   __typename?: 'ChatPosition';
   height: Scalars['Float']['output'];
@@ -48,40 +75,161 @@ export type ChatPositionInput = {
   y: Scalars['Float']['input'];
 };
 
+/** Which CSV header holds each story field. Headers match case-insensitively. */
+export type CsvColumnMapping = {
+  description?: Scalars['String']['input'];
+  externalKey?: Scalars['String']['input'];
+  title?: Scalars['String']['input'];
+};
+
 export type Deck = {
   __typename?: 'Deck';
+  cardDetails: Array<DeckCard>;
+  /** Card labels, in deck order. */
   cards: Array<Scalars['String']['output']>;
   id: Scalars['UUID']['output'];
+  /** The catalog deck these cards came from, if any. */
+  preset?: Maybe<DeckPreset>;
+};
+
+/** A card of a deck. Votes refer to cards by `label`. */
+export type DeckCard = {
+  __typename?: 'DeckCard';
+  /** Any CSS color. */
+  color?: Maybe<Scalars['String']['output']>;
+  emoji?: Maybe<Scalars['String']['output']>;
+  label: Scalars['String']['output'];
+  special?: Maybe<SpecialCard>;
+  /** What the card counts as in statistics; `None` for cards that don't count. */
+  value?: Maybe<Scalars['Float']['output']>;
+};
+
+export type DeckCardInput = {
+  color?: InputMaybe<Scalars['String']['input']>;
+  emoji?: InputMaybe<Scalars['String']['input']>;
+  label: Scalars['String']['input'];
+  /** Special cards never count, so they can't have a `value`. */
+  special?: InputMaybe<SpecialCard>;
+  value?: InputMaybe<Scalars['Float']['input']>;
+};
+
+export type DeckChangedPayload = {
+  __typename?: 'DeckChangedPayload';
+  deck: Deck;
+};
+
+/** A built-in deck from the server catalog. */
+export enum DeckPreset {
+  Fibonacci = 'FIBONACCI',
+  FistOfFive = 'FIST_OF_FIVE',
+  Hours = 'HOURS',
+  ModifiedFibonacci = 'MODIFIED_FIBONACCI',
+  PowersOfTwo = 'POWERS_OF_TWO',
+  TShirt = 'T_SHIRT'
+}
+
+/** A catalog entry, as listed by the `decks` query. */
+export type DeckPresetInfo = {
+  __typename?: 'DeckPresetInfo';
+  cardDetails: Array<DeckCard>;
+  /** Card labels, as on `Deck.cards`. */
+  cards: Array<Scalars['String']['output']>;
+  name: Scalars['String']['output'];
+  preset: DeckPreset;
+};
+
+export type DeckUpdated = {
+  __typename?: 'DeckUpdated';
+  deck: Deck;
+};
+
+export type FinalEstimateSetPayload = {
+  __typename?: 'FinalEstimateSetPayload';
+  /** `None` when the estimate was cleared. */
+  card?: Maybe<Scalars['String']['output']>;
+  setBy: Scalars['UUID']['output'];
+  storyId?: Maybe<Scalars['UUID']['output']>;
 };
 
 export type Game = {
   __typename?: 'Game';
   id: Scalars['UUID']['output'];
+  /** Computed when the cards are revealed; `None` until then. */
+  statistics?: Maybe<VoteStatistics>;
   table: Array<UserCard>;
 };
 
+export type GameResetPayload = {
+  __typename?: 'GameResetPayload';
+  /** The new, empty game. */
+  gameId: Scalars['UUID']['output'];
+  /** `None` when the round was reset automatically after the reveal. */
+  resetBy?: Maybe<Scalars['UUID']['output']>;
+};
+
+export type ImportStoriesInput = {
+  /** CSV only; defaults to the `title`, `description` and `key` columns. */
+  columns?: InputMaybe<CsvColumnMapping>;
+  /** The CSV or JSON document itself. */
+  data: Scalars['String']['input'];
+  format: StoryImportFormat;
+  roomId: Scalars['UUID']['input'];
+};
+
 export type MutationRoot = {
   __typename?: 'MutationRoot';
+  addStory: Room;
   banUser: Room;
   cancelRevealCountdown: Room;
+  /**
+   * Create a room. Clients may pick its `roomId`, but never that of a room
+   * that already exists. Without `cards`, `cardDetails` or a `preset` (or with
+   * no `cards`) it gets the default deck.
+   */
   createRoom: Room;
-  createUser: User;
+  /** Create a user and the session token that identifies them on later requests. */
+  createUser: UserSession;
+  deleteStory: Room;
   editUser: User;
+  /** Append stories from a CSV or JSON export to the backlog. */
+  importStories: StoryImportResult;
   joinRoom: Room;
   kickUser: Room;
   logout: Scalars['Boolean']['output'];
   markChatSeen: Room;
   pickCard: Room;
   renameRoom: Room;
+  /** Reorder the backlog; `story_ids` must list every story in the room once. */
+  reorderStories: Room;
   resetGame: Room;
+  /** Choose the story to vote on; `null` clears the selection. */
+  selectStory: Room;
   sendChatMessage: ChatMessage;
+  /** Record the estimate the team agreed on for the revealed round; `null` clears it. */
+  setFinalEstimate: Room;
   setRoomOwner: Room;
+  /** Set, change or (with `null` or an empty string) remove the room passcode. */
+  setRoomPasscode: Room;
+  /** Name the story being estimated; it is recorded with the round when revealed. */
+  setRoundTitle: Room;
+  /**
+   * Switch a user between spectating and voting. Anyone can switch
+   * themselves; switching someone else takes the owner.
+   */
+  setSpectator: Room;
   showCards: Room;
   startRevealCountdown: Room;
   toggleConfirmNewGame: Room;
   toggleCountdownOption: Room;
   unbanUser: Room;
   updateDeck: Room;
+  updateRoomSettings: Room;
+  updateStory: Room;
+};
+
+
+export type MutationRootAddStoryArgs = {
+  input: AddStoryInput;
 };
 
 
@@ -93,13 +241,15 @@ export type MutationRootBanUserArgs = {
 
 export type MutationRootCancelRevealCountdownArgs = {
   roomId: Scalars['UUID']['input'];
-  userId?: InputMaybe<Scalars['UUID']['input']>;
 };
 
 
 export type MutationRootCreateRoomArgs = {
-  cards: Array<Scalars['String']['input']>;
+  cardDetails?: InputMaybe<Array<DeckCardInput>>;
+  cards?: InputMaybe<Array<Scalars['String']['input']>>;
   name?: InputMaybe<Scalars['String']['input']>;
+  passcode?: InputMaybe<Scalars['String']['input']>;
+  preset?: InputMaybe<DeckPreset>;
   roomId?: InputMaybe<Scalars['UUID']['input']>;
 };
 
@@ -109,13 +259,24 @@ export type MutationRootCreateUserArgs = {
 };
 
 
+export type MutationRootDeleteStoryArgs = {
+  roomId: Scalars['UUID']['input'];
+  storyId: Scalars['UUID']['input'];
+};
+
+
 export type MutationRootEditUserArgs = {
-  userId: Scalars['UUID']['input'];
   username: Scalars['String']['input'];
 };
 
 
+export type MutationRootImportStoriesArgs = {
+  input: ImportStoriesInput;
+};
+
+
 export type MutationRootJoinRoomArgs = {
+  passcode?: InputMaybe<Scalars['String']['input']>;
   roomId: Scalars['UUID']['input'];
   roomOwnerId?: InputMaybe<Scalars['UUID']['input']>;
   user: UserInput;
@@ -128,21 +289,14 @@ export type MutationRootKickUserArgs = {
 };
 
 
-export type MutationRootLogoutArgs = {
-  userId?: InputMaybe<Scalars['UUID']['input']>;
-};
-
-
 export type MutationRootMarkChatSeenArgs = {
   roomId: Scalars['UUID']['input'];
-  userId: Scalars['UUID']['input'];
 };
 
 
 export type MutationRootPickCardArgs = {
   card: Scalars['String']['input'];
   roomId: Scalars['UUID']['input'];
-  userId: Scalars['UUID']['input'];
 };
 
 
@@ -152,22 +306,59 @@ export type MutationRootRenameRoomArgs = {
 };
 
 
+export type MutationRootReorderStoriesArgs = {
+  roomId: Scalars['UUID']['input'];
+  storyIds: Array<Scalars['UUID']['input']>;
+};
+
+
 export type MutationRootResetGameArgs = {
   roomId: Scalars['UUID']['input'];
 };
 
 
+export type MutationRootSelectStoryArgs = {
+  roomId: Scalars['UUID']['input'];
+  storyId?: InputMaybe<Scalars['UUID']['input']>;
+};
+
+
 export type MutationRootSendChatMessageArgs = {
   input: SendChatInput;
 };
 
 
+export type MutationRootSetFinalEstimateArgs = {
+  card?: InputMaybe<Scalars['String']['input']>;
+  roomId: Scalars['UUID']['input'];
+};
+
+
 export type MutationRootSetRoomOwnerArgs = {
   roomId: Scalars['UUID']['input'];
   userId?: InputMaybe<Scalars['UUID']['input']>;
 };
 
 
+export type MutationRootSetRoomPasscodeArgs = {
+  passcode?: InputMaybe<Scalars['String']['input']>;
+  roomId: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootSetRoundTitleArgs = {
+  roomId: Scalars['UUID']['input'];
+  title?: InputMaybe<Scalars['String']['input']>;
+};
+
+
+export type MutationRootSetSpectatorArgs = {
+  roomId: Scalars['UUID']['input'];
+  spectator: Scalars['Boolean']['input'];
+  targetUserId?: InputMaybe<Scalars['UUID']['input']>;
+};
+
+
 export type MutationRootShowCardsArgs = {
   roomId: Scalars['UUID']['input'];
 };
@@ -175,7 +366,6 @@ export type MutationRootShowCardsArgs = {
 
 export type MutationRootStartRevealCountdownArgs = {
   roomId: Scalars['UUID']['input'];
-  userId?: InputMaybe<Scalars['UUID']['input']>;
 };
 
 
@@ -201,11 +391,46 @@ export type MutationRootUpdateDeckArgs = {
   input: UpdateDeckInput;
 };
 
+
+export type MutationRootUpdateRoomSettingsArgs = {
+  input: RoomSettingsInput;
+};
+
+
+export type MutationRootUpdateStoryArgs = {
+  input: UpdateStoryInput;
+};
+
+export type OwnerChangedPayload = {
+  __typename?: 'OwnerChangedPayload';
+  ownerId?: Maybe<Scalars['UUID']['output']>;
+  previousOwnerId?: Maybe<Scalars['UUID']['output']>;
+};
+
 export type QueryRoot = {
   __typename?: 'QueryRoot';
+  /** The built-in decks `createRoom` and `updateDeck` accept as `preset`. */
+  decks: Array<DeckPresetInfo>;
+  /**
+   * A room the caller belongs to; `null` if it doesn't exist or they aren't a
+   * member, so use `roomPreview` to tell those apart.
+   */
   roomById?: Maybe<Room>;
-  rooms: Array<Room>;
-  userRooms: Array<Room>;
+  /**
+   * What someone may learn of a room before joining it: whether it exists,
+   * its name and whether it takes a passcode. Needs no session.
+   */
+  roomPreview: RoomPreview;
+  /**
+   * Resync after a gap in `roomChanges`: the room's current version, and the
+   * room itself unless it is still at `sinceVersion`. Fails with
+   * `ROOM_NOT_FOUND` or `NOT_ROOM_MEMBER`.
+   */
+  roomSync: RoomSync;
+  /** Every room on the instance. Admin only. */
+  rooms: Array<RoomSummary>;
+  /** The rooms a user is in. Callers may only list their own, unless they are admins. */
+  userRooms: Array<RoomSummary>;
 };
 
 
@@ -214,26 +439,73 @@ export type QueryRootRoomByIdArgs = {
 };
 
 
+export type QueryRootRoomPreviewArgs = {
+  roomId: Scalars['UUID']['input'];
+};
+
+
+export type QueryRootRoomSyncArgs = {
+  roomId: Scalars['UUID']['input'];
+  sinceVersion: Scalars['Int']['input'];
+};
+
+
 export type QueryRootUserRoomsArgs = {
   userId: Scalars['UUID']['input'];
 };
 
+/** Where the current round is on its way to a reveal. */
+export enum RevealStage {
+  Cancelled = 'CANCELLED',
+  Countdown = 'COUNTDOWN',
+  Idle = 'IDLE',
+  Revealed = 'REVEALED'
+}
+
+/** The reveal countdown started, ticked or was cancelled. */
+export type RevealStageChanged = {
+  __typename?: 'RevealStageChanged';
+  countdownValue?: Maybe<Scalars['Int']['output']>;
+  revealStage?: Maybe<RevealStage>;
+};
+
 export type Room = {
   __typename?: 'Room';
+  /** Start a new round this many seconds after a reveal; `None` disables it. */
+  autoResetSeconds?: Maybe<Scalars['Int']['output']>;
+  /** Reveal automatically once every user in the room has voted. */
+  autoReveal: Scalars['Boolean']['output'];
   bannedUsers: Array<Scalars['UUID']['output']>;
   chatHistory: Array<ChatMessage>;
   confirmNewGame: Scalars['Boolean']['output'];
   countdownEnabled: Scalars['Boolean']['output'];
+  /** Length of the reveal countdown, in seconds. */
+  countdownSeconds: Scalars['Int']['output'];
   countdownValue?: Maybe<Scalars['Int']['output']>;
+  /** The story being voted on. */
+  currentStoryId?: Maybe<Scalars['UUID']['output']>;
   deck: Deck;
   game: Game;
+  /** Whether joining the room requires a passcode. */
+  hasPasscode: Scalars['Boolean']['output'];
   hasUnreadChat?: Maybe<Scalars['Boolean']['output']>;
   id: Scalars['UUID']['output'];
   isGameOver: Scalars['Boolean']['output'];
   name?: Maybe<Scalars['String']['output']>;
-  revealStage?: Maybe<Scalars['String']['output']>;
+  revealStage?: Maybe<RevealStage>;
   roomOwnerId?: Maybe<Scalars['UUID']['output']>;
+  roundCount: Scalars['Int']['output'];
+  /** Title of the story being estimated in the current round. */
+  roundTitle?: Maybe<Scalars['String']['output']>;
+  /** Revealed rounds, newest first. */
+  rounds: Array<Round>;
+  /** Stories to estimate, in backlog order. */
+  stories: Array<Story>;
   users: Array<User>;
+  /** Bumped by one on every change; see `RoomChange`. */
+  version: Scalars['Int']['output'];
+  /** When votes stop being accepted. */
+  voteLock: VoteLockPolicy;
 };
 
 
@@ -241,27 +513,221 @@ export type RoomHasUnreadChatArgs = {
   userId: Scalars['UUID']['input'];
 };
 
+
+export type RoomRoundsArgs = {
+  limit?: InputMaybe<Scalars['Int']['input']>;
+  offset?: InputMaybe<Scalars['Int']['input']>;
+};
+
+/** One change to a room, as published on the `roomChanges` subscription. */
+export type RoomChange = {
+  __typename?: 'RoomChange';
+  delta: RoomDelta;
+  roomId: Scalars['UUID']['output'];
+  /**
+   * The room version after this change. Versions go up by exactly one per
+   * change, so a gap means changes were missed; resync with
+   * `roomSync(roomId, sinceVersion)`.
+   */
+  version: Scalars['Int']['output'];
+};
+
+export type RoomDelta = CardsRevealed | ChatMessageSent | DeckUpdated | RevealStageChanged | RoomReplaced | SettingsChanged | UserJoined | UserLeft | UserUpdated | VoteCast;
+
+/** Something that happened in a room, as published on `roomEvents`. */
 export type RoomEvent = {
   __typename?: 'RoomEvent';
-  eventType: Scalars['String']['output'];
+  /** Matches the type of `payload`. */
+  eventType: RoomEventType;
+  payload: RoomEventPayload;
+  /** The room after the event. */
   room: Room;
   roomId: Scalars['UUID']['output'];
+  /** The user the event is about, for kicks, bans and presence changes. */
   targetUserId?: Maybe<Scalars['UUID']['output']>;
 };
 
+export type RoomEventPayload = CardsRevealedPayload | DeckChangedPayload | FinalEstimateSetPayload | GameResetPayload | OwnerChangedPayload | RoomExpiredPayload | RoomRenamedPayload | UserBannedPayload | UserKickedPayload | UserOfflinePayload | UserOnlinePayload | UserTimedOutPayload;
+
+export enum RoomEventType {
+  CardsRevealed = 'CARDS_REVEALED',
+  DeckChanged = 'DECK_CHANGED',
+  FinalEstimateSet = 'FINAL_ESTIMATE_SET',
+  GameReset = 'GAME_RESET',
+  OwnerChanged = 'OWNER_CHANGED',
+  RoomExpired = 'ROOM_EXPIRED',
+  RoomRenamed = 'ROOM_RENAMED',
+  UserBanned = 'USER_BANNED',
+  UserKicked = 'USER_KICKED',
+  UserOffline = 'USER_OFFLINE',
+  UserOnline = 'USER_ONLINE',
+  UserTimedOut = 'USER_TIMED_OUT'
+}
+
+/** The room was removed for inactivity; it can't be rejoined. */
+export type RoomExpiredPayload = {
+  __typename?: 'RoomExpiredPayload';
+  roomName?: Maybe<Scalars['String']['output']>;
+};
+
+/** A room as seen from outside it. */
+export type RoomPreview = {
+  __typename?: 'RoomPreview';
+  exists: Scalars['Boolean']['output'];
+  hasPasscode: Scalars['Boolean']['output'];
+  name?: Maybe<Scalars['String']['output']>;
+};
+
+export type RoomRenamedPayload = {
+  __typename?: 'RoomRenamedPayload';
+  name?: Maybe<Scalars['String']['output']>;
+};
+
+export type RoomReplaced = {
+  __typename?: 'RoomReplaced';
+  room: Room;
+};
+
+export type RoomSettingsInput = {
+  /** Seconds after a reveal before a new round starts; `null` turns it off. */
+  autoResetSeconds?: InputMaybe<Scalars['Int']['input']>;
+  /** Reveal automatically once everyone has voted. */
+  autoReveal?: InputMaybe<Scalars['Boolean']['input']>;
+  /** Reveal countdown length in seconds (3-10). */
+  countdownSeconds?: InputMaybe<Scalars['Int']['input']>;
+  roomId: Scalars['UUID']['input'];
+  /** When votes stop being accepted. */
+  voteLock?: InputMaybe<VoteLockPolicy>;
+};
+
+/** What listings show about a room: no members, chat or cards. */
+export type RoomSummary = {
+  __typename?: 'RoomSummary';
+  hasPasscode: Scalars['Boolean']['output'];
+  id: Scalars['UUID']['output'];
+  isGameOver: Scalars['Boolean']['output'];
+  lastActive: Scalars['DateTime']['output'];
+  name?: Maybe<Scalars['String']['output']>;
+  roomOwnerId?: Maybe<Scalars['UUID']['output']>;
+  userCount: Scalars['Int']['output'];
+};
+
+/** A room's version, with the room when the caller's copy is out of date. */
+export type RoomSync = {
+  __typename?: 'RoomSync';
+  /** `null` when the room hasn't changed since `sinceVersion`. */
+  room?: Maybe<Room>;
+  version: Scalars['Int']['output'];
+};
+
+/** A revealed round, archived on the room. */
+export type Round = {
+  __typename?: 'Round';
+  /** The estimate the team agreed on, which may differ from every vote. */
+  finalEstimate?: Maybe<Scalars['String']['output']>;
+  finalEstimateAt?: Maybe<Scalars['DateTime']['output']>;
+  finalEstimateBy?: Maybe<Scalars['UUID']['output']>;
+  /** The `Game` this round was played as. */
+  gameId: Scalars['UUID']['output'];
+  /** 1-based, counting every round revealed in the room. */
+  number: Scalars['Int']['output'];
+  revealedAt: Scalars['DateTime']['output'];
+  /** Who revealed the cards (or started the countdown); `None` when it happened automatically. */
+  revealedBy?: Maybe<Scalars['UUID']['output']>;
+  statistics?: Maybe<VoteStatistics>;
+  /** The backlog story voted on, if one was selected. */
+  storyId?: Maybe<Scalars['UUID']['output']>;
+  storyTitle?: Maybe<Scalars['String']['output']>;
+  votes: Array<UserCard>;
+};
+
 export type SendChatInput = {
   content: Scalars['String']['input'];
   contentType: Scalars['String']['input'];
   formattedContent?: InputMaybe<Scalars['String']['input']>;
   position?: InputMaybe<ChatPositionInput>;
   roomId: Scalars['UUID']['input'];
-  userId: Scalars['UUID']['input'];
-  username: Scalars['String']['input'];
 };
 
+/** The room's name and settings, all sent together whichever changed. */
+export type SettingsChanged = {
+  __typename?: 'SettingsChanged';
+  autoResetSeconds?: Maybe<Scalars['Int']['output']>;
+  autoReveal: Scalars['Boolean']['output'];
+  confirmNewGame: Scalars['Boolean']['output'];
+  countdownEnabled: Scalars['Boolean']['output'];
+  countdownSeconds: Scalars['Int']['output'];
+  hasPasscode: Scalars['Boolean']['output'];
+  name?: Maybe<Scalars['String']['output']>;
+  voteLock: VoteLockPolicy;
+};
+
+/** A card that stands for something other than an estimate. */
+export enum SpecialCard {
+  /** "I need a break" (☕). */
+  Break = 'BREAK',
+  /** "I don't know" (?). */
+  Unknown = 'UNKNOWN'
+}
+
+/** An item of the room backlog. */
+export type Story = {
+  __typename?: 'Story';
+  description?: Maybe<Scalars['String']['output']>;
+  /** Key in an external tracker, e.g. `PROJ-123`. */
+  externalKey?: Maybe<Scalars['String']['output']>;
+  finalEstimate?: Maybe<Scalars['String']['output']>;
+  id: Scalars['UUID']['output'];
+  status: StoryStatus;
+  title: Scalars['String']['output'];
+};
+
+/** A row that couldn't be imported. */
+export type StoryImportError = {
+  __typename?: 'StoryImportError';
+  message: Scalars['String']['output'];
+  /** Line number for CSV, 1-based array index for JSON. */
+  row: Scalars['Int']['output'];
+};
+
+export enum StoryImportFormat {
+  /** Comma-separated values with a header row; see [`CsvColumnMapping`]. */
+  Csv = 'CSV',
+  /** An array of `{ "title", "description", "externalKey" }` objects. */
+  Json = 'JSON'
+}
+
+/** Outcome of a backlog import: rejected rows don't stop the others. */
+export type StoryImportResult = {
+  __typename?: 'StoryImportResult';
+  errors: Array<StoryImportError>;
+  importedCount: Scalars['Int']['output'];
+  room: Room;
+};
+
+export enum StoryStatus {
+  /** Voted on and left behind. */
+  Estimated = 'ESTIMATED',
+  /** Waiting its turn in the backlog. */
+  Pending = 'PENDING',
+  /** Passed over without an estimate. */
+  Skipped = 'SKIPPED',
+  /** Currently being voted on. */
+  Voting = 'VOTING'
+}
+
+/**
+ * Every subscription is for the room's members: subscribing needs a session
+ * token in `connection_init` and fails with `UNAUTHENTICATED`, `ROOM_NOT_FOUND`
+ * or `NOT_ROOM_MEMBER` otherwise. A stream ends once its subscriber is no longer
+ * in the room; `roomEvents` still delivers the event telling a kicked or banned
+ * user so before it does.
+ */
 export type SubscriptionRoot = {
   __typename?: 'SubscriptionRoot';
   room: Room;
+  /** Typed changes to the room, lighter than the full snapshots of `room`. */
+  roomChanges: RoomChange;
   roomChat: ChatMessage;
   roomEvents: RoomEvent;
 };
@@ -272,6 +738,11 @@ export type SubscriptionRootRoomArgs = {
 };
 
 
+export type SubscriptionRootRoomChangesArgs = {
+  roomId: Scalars['UUID']['input'];
+};
+
+
 export type SubscriptionRootRoomChatArgs = {
   roomId: Scalars['UUID']['input'];
 };
@@ -282,19 +753,48 @@ export type SubscriptionRootRoomEventsArgs = {
 };
 
 export type UpdateDeckInput = {
-  cards: Array<Scalars['String']['input']>;
+  cardDetails?: InputMaybe<Array<DeckCardInput>>;
+  /**
+   * Give at most one of `cards` (plain labels), `cardDetails` or `preset`;
+   * neither, or no `cards`, means the default deck.
+   */
+  cards?: InputMaybe<Array<Scalars['String']['input']>>;
+  preset?: InputMaybe<DeckPreset>;
   roomId: Scalars['UUID']['input'];
 };
 
+/** Fields left out are unchanged; `null` clears an optional field. */
+export type UpdateStoryInput = {
+  description?: InputMaybe<Scalars['String']['input']>;
+  externalKey?: InputMaybe<Scalars['String']['input']>;
+  roomId: Scalars['UUID']['input'];
+  /** `VOTING` selects the story; anything else deselects it if it was selected. */
+  status?: InputMaybe<StoryStatus>;
+  storyId: Scalars['UUID']['input'];
+  title?: InputMaybe<Scalars['String']['input']>;
+};
+
 export type User = {
   __typename?: 'User';
   id: Scalars['UUID']['output'];
+  /** Spectators watch and chat but don't vote or count towards the vote. */
+  isSpectator: Scalars['Boolean']['output'];
   lastCardPicked?: Maybe<Scalars['String']['output']>;
   lastCardValue?: Maybe<Scalars['Float']['output']>;
+  /** When the user last connected to or disconnected from the room. */
+  lastSeen?: Maybe<Scalars['DateTime']['output']>;
   lastSeenChatMessageId?: Maybe<Scalars['UUID']['output']>;
+  /** Whether the user has a live subscription to the room. */
+  online: Scalars['Boolean']['output'];
   username: Scalars['String']['output'];
 };
 
+export type UserBannedPayload = {
+  __typename?: 'UserBannedPayload';
+  bannedBy: Scalars['UUID']['output'];
+  userId: Scalars['UUID']['output'];
+};
+
 export type UserCard = {
   __typename?: 'UserCard';
   card?: Maybe<Scalars['String']['output']>;
@@ -302,8 +802,104 @@ export type UserCard = {
 };
 
 export type UserInput = {
-  id: Scalars['UUID']['input'];
+  /** Join as a spectator: watch and chat, but don't vote. */
+  isSpectator?: InputMaybe<Scalars['Boolean']['input']>;
   lastCardPicked?: InputMaybe<Scalars['String']['input']>;
   roomName?: InputMaybe<Scalars['String']['input']>;
   username: Scalars['String']['input'];
 };
+
+export type UserJoined = {
+  __typename?: 'UserJoined';
+  user: User;
+};
+
+export type UserKickedPayload = {
+  __typename?: 'UserKickedPayload';
+  kickedBy: Scalars['UUID']['output'];
+  userId: Scalars['UUID']['output'];
+};
+
+/** The user left, was kicked or was banned; their card leaves the table too. */
+export type UserLeft = {
+  __typename?: 'UserLeft';
+  banned: Scalars['Boolean']['output'];
+  userId: Scalars['UUID']['output'];
+};
+
+export type UserOfflinePayload = {
+  __typename?: 'UserOfflinePayload';
+  lastSeen?: Maybe<Scalars['DateTime']['output']>;
+  userId: Scalars['UUID']['output'];
+};
+
+export type UserOnlinePayload = {
+  __typename?: 'UserOnlinePayload';
+  userId: Scalars['UUID']['output'];
+};
+
+/**
+ * A freshly created user and the token to send as `Authorization: Bearer <token>`
+ * (or as `authToken` in the WebSocket `connection_init` payload).
+ */
+export type UserSession = {
+  __typename?: 'UserSession';
+  token: Scalars['String']['output'];
+  user: User;
+};
+
+/** The user stayed disconnected past the grace period and was removed. */
+export type UserTimedOutPayload = {
+  __typename?: 'UserTimedOutPayload';
+  userId: Scalars['UUID']['output'];
+};
+
+export type UserUpdated = {
+  __typename?: 'UserUpdated';
+  user: User;
+};
+
+export type VoteCast = {
+  __typename?: 'VoteCast';
+  /** The card, only once the round has been revealed. */
+  card?: Maybe<Scalars['String']['output']>;
+  userId: Scalars['UUID']['output'];
+  /** `false` when the user took their card back. */
+  voted: Scalars['Boolean']['output'];
+};
+
+/** When a round stops accepting vote changes. */
+export enum VoteLockPolicy {
+  /** Votes freeze as soon as the reveal countdown starts. */
+  AtCountdown = 'AT_COUNTDOWN',
+  /** Votes can change during the countdown but not after the reveal. */
+  AtReveal = 'AT_REVEAL',
+  /** Votes can change at any time, even after the reveal. */
+  Never = 'NEVER'
+}
+
+/**
+ * Summary of a revealed round's votes.
+ *
+ * Only cards with a value in the deck count towards the averages; the rest
+ * ("?", "☕", ...) are reported in `non_numeric_count`. Numeric fields are `None` when nobody picked
+ * a numeric card.
+ */
+export type VoteStatistics = {
+  __typename?: 'VoteStatistics';
+  /** Everyone picked the same card. */
+  consensus: Scalars['Boolean']['output'];
+  max?: Maybe<Scalars['Float']['output']>;
+  mean?: Maybe<Scalars['Float']['output']>;
+  median?: Maybe<Scalars['Float']['output']>;
+  min?: Maybe<Scalars['Float']['output']>;
+  /** Most frequent values, ascending; several on a tie. */
+  mode: Array<Scalars['Float']['output']>;
+  /** The numeric deck card closest to the mean. */
+  nearestCard?: Maybe<Scalars['String']['output']>;
+  nonNumericCount: Scalars['Int']['output'];
+  numericCount: Scalars['Int']['output'];
+  /** Population standard deviation. */
+  stdDev?: Maybe<Scalars['Float']['output']>;
+  voteCount: Scalars['Int']['output'];
+};
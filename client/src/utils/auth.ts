@@ -1,4 +1,4 @@
-import { USER_KEY } from "@/settings";
+import { TOKEN_KEY, USER_KEY } from "@/settings";
 import { User } from "@/types";
 
 export function getUserFromLocalStorage(): User | null {
@@ -21,6 +21,37 @@ export function removeUserFromLocalStorage(): void {
   localStorage.removeItem(USER_KEY);
 }
 
+export function getTokenFromLocalStorage(): string | null {
+  return localStorage.getItem(TOKEN_KEY);
+}
+
+export function setTokenToLocalStorage(token: string): void {
+  localStorage.setItem(TOKEN_KEY, token);
+}
+
+export function removeTokenFromLocalStorage(): void {
+  localStorage.removeItem(TOKEN_KEY);
+}
+
 export function removeRoomFromLocalStorage(): void {
   localStorage.removeItem("Room");
 }
+
+type SessionExpiredListener = () => void;
+
+const sessionExpiredListeners = new Set<SessionExpiredListener>();
+
+/** Get notified when the server stops accepting the stored session. */
+export function onSessionExpired(listener: SessionExpiredListener): () => void {
+  sessionExpiredListeners.add(listener);
+  return () => {
+    sessionExpiredListeners.delete(listener);
+  };
+}
+
+/** Forget a user the server no longer recognises, so they create a new one. */
+export function expireSession(): void {
+  removeUserFromLocalStorage();
+  removeTokenFromLocalStorage();
+  sessionExpiredListeners.forEach((listener) => listener());
+}
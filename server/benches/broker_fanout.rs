@@ -0,0 +1,64 @@
+//! Cost of publishing a room snapshot while other rooms have subscribers.
+//!
+//! The broker routes by room, so publishing to one room should cost the same
+//! whether it is the only room being watched or one of thousands.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures::{FutureExt, StreamExt};
+use planning_poker_server::{
+    domain::{room::Room, user::User},
+    simple_broker::SimpleBroker,
+};
+use uuid::Uuid;
+
+const SUBSCRIBERS_PER_ROOM: usize = 4;
+
+fn watched_room() -> Room {
+    let mut room = Room::new(
+        Some("watched".to_string()),
+        ["1", "2", "3", "5", "8", "13"].map(String::from).to_vec(),
+    );
+    for u in 0..8 {
+        room.users.push(User::new(format!("user {u}")));
+    }
+    room
+}
+
+fn publish_to_one_room(c: &mut Criterion) {
+    let mut group = c.benchmark_group("publish_to_one_room");
+    let room = watched_room();
+
+    for unrelated_rooms in [0usize, 100, 1_000, 10_000] {
+        let _unrelated: Vec<_> = (0..unrelated_rooms * SUBSCRIBERS_PER_ROOM)
+            .map(|i| {
+                // Consecutive subscribers share a room.
+                let topic = Uuid::from_u128((i / SUBSCRIBERS_PER_ROOM) as u128 + 1);
+                SimpleBroker::<Room>::subscribe(topic)
+            })
+            .collect();
+        let mut watchers: Vec<_> = (0..SUBSCRIBERS_PER_ROOM)
+            .map(|_| SimpleBroker::<Room>::subscribe(room.id))
+            .collect();
+
+        assert_eq!(SimpleBroker::<Room>::topic_count(), unrelated_rooms + 1);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(unrelated_rooms),
+            &room,
+            |b, room| {
+                b.iter(|| {
+                    SimpleBroker::publish(room.get_room());
+                    // Keep the queues from growing across iterations.
+                    for watcher in &mut watchers {
+                        watcher.next().now_or_never();
+                    }
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, publish_to_one_room);
+criterion_main!(benches);
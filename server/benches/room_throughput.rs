@@ -0,0 +1,122 @@
+//! Throughput of concurrent room mutations with hundreds of active rooms.
+//!
+//! Compares the per-room locking repository against a single mutex over the
+//! whole room map (the previous storage layout).
+
+use std::{collections::HashMap, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use planning_poker_server::{
+    domain::{room::Room, user::User},
+    persistence::MemoryStore,
+    repository::{InMemoryRoomRepository, RoomRepository},
+    types::EntityId,
+};
+use tokio::sync::Mutex;
+
+const USERS_PER_ROOM: usize = 8;
+const VOTES_PER_ROOM: usize = 50;
+
+fn seed_rooms(count: usize) -> Vec<Room> {
+    (0..count)
+        .map(|i| {
+            let mut room = Room::new(
+                Some(format!("room {i}")),
+                ["1", "2", "3", "5", "8", "13"].map(String::from).to_vec(),
+            );
+            for u in 0..USERS_PER_ROOM {
+                room.users.push(User::new(format!("user {u}")));
+            }
+            room
+        })
+        .collect()
+}
+
+/// What a `pick_card` resolver does while it holds a room.
+fn vote(room: &mut Room, round: usize) -> Room {
+    let user_id = room.users[round % room.users.len()].id;
+    let card = room.deck.cards[round % room.deck.cards.len()].label.clone();
+
+    room.cast_vote(user_id, &card).unwrap();
+    room.touch();
+
+    room.get_room()
+}
+
+async fn run_per_room(repository: Arc<dyn RoomRepository>, ids: &[EntityId]) {
+    let tasks: Vec<_> = ids
+        .iter()
+        .map(|&room_id| {
+            let repository = repository.clone();
+            tokio::spawn(async move {
+                for round in 0..VOTES_PER_ROOM {
+                    repository
+                        .update_with(room_id, |room| Ok(vote(room, round)))
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+async fn run_global_lock(storage: Arc<Mutex<HashMap<EntityId, Room>>>, ids: &[EntityId]) {
+    let tasks: Vec<_> = ids
+        .iter()
+        .map(|&room_id| {
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                for round in 0..VOTES_PER_ROOM {
+                    let mut rooms = storage.lock().await;
+                    vote(rooms.get_mut(&room_id).unwrap(), round);
+                }
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+fn concurrent_votes(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("concurrent_votes");
+
+    for room_count in [100usize, 300, 500] {
+        let rooms = seed_rooms(room_count);
+        let ids: Vec<EntityId> = rooms.iter().map(|room| room.id).collect();
+
+        group.throughput(Throughput::Elements((room_count * VOTES_PER_ROOM) as u64));
+
+        let repository: Arc<dyn RoomRepository> = Arc::new(InMemoryRoomRepository::with_rooms(
+            Arc::new(MemoryStore),
+            rooms.clone(),
+        ));
+        group.bench_with_input(BenchmarkId::new("per_room_lock", room_count), &ids, |b, ids| {
+            b.to_async(&runtime)
+                .iter(|| run_per_room(repository.clone(), ids));
+        });
+
+        let storage = Arc::new(Mutex::new(
+            rooms.into_iter().map(|room| (room.id, room)).collect::<HashMap<_, _>>(),
+        ));
+        group.bench_with_input(BenchmarkId::new("global_lock", room_count), &ids, |b, ids| {
+            b.to_async(&runtime)
+                .iter(|| run_global_lock(storage.clone(), ids));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, concurrent_votes);
+criterion_main!(benches);
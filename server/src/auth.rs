@@ -0,0 +1,125 @@
+use async_graphql::{Context, Error, ErrorExtensions, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::types::EntityId;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HTTP header carrying the admin token.
+pub const ADMIN_TOKEN_HEADER: &str = "X-Admin-Token";
+
+/// Name of the WebSocket `connection_init` payload field carrying the session token.
+pub const CONNECTION_TOKEN_KEY: &str = "authToken";
+
+/// Issues and verifies session tokens.
+///
+/// A token is `<user id>.<signature>`, where the signature is an HMAC-SHA256 of
+/// the user ID under the server secret. Nothing is stored server-side, so tokens
+/// stay valid across restarts as long as the secret doesn't change.
+#[derive(Clone)]
+pub struct SessionSigner {
+    secret: Vec<u8>,
+}
+
+impl SessionSigner {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        SessionSigner {
+            secret: secret.into(),
+        }
+    }
+
+    /// A signer with a random secret; tokens won't survive a restart.
+    pub fn random() -> Self {
+        let mut secret = Vec::with_capacity(32);
+        secret.extend_from_slice(Uuid::new_v4().as_bytes());
+        secret.extend_from_slice(Uuid::new_v4().as_bytes());
+        Self::new(secret)
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length")
+    }
+
+    pub fn issue(&self, user_id: EntityId) -> String {
+        let mut mac = self.mac();
+        mac.update(user_id.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+        format!("{}.{}", user_id, signature)
+    }
+
+    /// The user a token was issued to, if the token is genuine.
+    pub fn verify(&self, token: &str) -> Option<EntityId> {
+        let (user_id, signature) = token.trim().split_once('.')?;
+        let user_id = Uuid::parse_str(user_id).ok()?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+        let mut mac = self.mac();
+        mac.update(user_id.as_bytes());
+        mac.verify_slice(&signature).ok()?;
+
+        Some(user_id)
+    }
+
+    /// Resolve a `Bearer <token>` header value.
+    pub fn verify_bearer(&self, header: &str) -> Option<EntityId> {
+        let token = header.strip_prefix("Bearer ").unwrap_or(header);
+        self.verify(token)
+    }
+}
+
+/// Checks the admin token configured in `auth.admin_token`.
+#[derive(Clone, Default)]
+pub struct AdminCredential {
+    digest: Option<[u8; 32]>,
+}
+
+impl AdminCredential {
+    pub fn new(token: Option<&str>) -> Self {
+        AdminCredential {
+            digest: token.filter(|t| !t.is_empty()).map(|t| Sha256::digest(t).into()),
+        }
+    }
+
+    pub fn verify(&self, token: &str) -> bool {
+        // Comparing digests rather than raw tokens doesn't leak how much of the token matched.
+        self.digest
+            .is_some_and(|digest| digest == <[u8; 32]>::from(Sha256::digest(token)))
+    }
+}
+
+/// Marks a GraphQL request made with a valid admin token.
+#[derive(Clone, Copy, Debug)]
+pub struct AdminAccess;
+
+/// The caller's identity, attached to the GraphQL request by the HTTP and
+/// WebSocket handlers once their session token has been verified.
+#[derive(Clone, Copy, Debug)]
+pub struct AuthenticatedUser(pub EntityId);
+
+pub fn unauthenticated() -> Error {
+    Error::new("Not authenticated").extend_with(|_, e| e.set("code", "UNAUTHENTICATED"))
+}
+
+/// The authenticated caller, or an `UNAUTHENTICATED` error.
+pub fn require_user(ctx: &Context<'_>) -> Result<EntityId> {
+    ctx.data_opt::<AuthenticatedUser>()
+        .map(|user| user.0)
+        .ok_or_else(unauthenticated)
+}
+
+pub fn is_admin(ctx: &Context<'_>) -> bool {
+    ctx.data_opt::<AdminAccess>().is_some()
+}
+
+pub fn admin_required() -> Error {
+    Error::new("Admin access required").extend_with(|_, e| e.set("code", "FORBIDDEN"))
+}
+
+/// Succeeds for admin requests, otherwise fails with a `FORBIDDEN` error.
+pub fn require_admin(ctx: &Context<'_>) -> Result<()> {
+    if is_admin(ctx) { Ok(()) } else { Err(admin_required()) }
+}
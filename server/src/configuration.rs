@@ -1,11 +1,31 @@
 use std::convert::{TryFrom, TryInto};
 
 use serde::Deserialize;
-use serde_aux::field_attributes::deserialize_number_from_string;
+use serde_aux::field_attributes::{
+    deserialize_number_from_string, deserialize_option_number_from_string,
+};
 
 #[derive(Deserialize, Clone)]
 pub struct Settings {
     pub application: ApplicationSettings,
+    #[serde(default)]
+    pub persistence: PersistenceSettings,
+    #[serde(default)]
+    pub auth: AuthSettings,
+    #[serde(default)]
+    pub broker: BrokerSettings,
+    #[serde(default)]
+    pub presence: PresenceSettings,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct AuthSettings {
+    /// Secret used to sign session tokens, so sessions survive a restart.
+    /// Required outside the local environment (`APP_AUTH__SESSION_SECRET`).
+    pub session_secret: Option<String>,
+    /// Token granting access to instance-wide queries such as `rooms`, sent as
+    /// the `X-Admin-Token` header. Those queries are disabled when unset.
+    pub admin_token: Option<String>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -15,6 +35,71 @@ pub struct ApplicationSettings {
     pub host: String,
 }
 
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PersistenceBackend {
+    /// Rooms live in memory only and are lost on restart.
+    #[default]
+    Memory,
+    /// Rooms are written through to an append-only log on disk.
+    File,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct PersistenceSettings {
+    pub backend: PersistenceBackend,
+    pub path: String,
+}
+
+impl Default for PersistenceSettings {
+    fn default() -> Self {
+        PersistenceSettings {
+            backend: PersistenceBackend::Memory,
+            path: "data/rooms.jsonl".to_string(),
+        }
+    }
+}
+
+/// What to do when a subscriber's queue is full because the client isn't
+/// reading fast enough.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowConsumerPolicy {
+    /// Discard the oldest queued message to make room.
+    DropOldest,
+    /// Room snapshots replace everything queued before them, since only the
+    /// latest state matters. Other messages fall back to dropping the oldest.
+    #[default]
+    Coalesce,
+    /// End the subscription; the client resubscribes and gets fresh state.
+    Disconnect,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BrokerSettings {
+    /// Messages queued per subscriber before `slow_consumer_policy` applies.
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub queue_capacity: usize,
+    pub slow_consumer_policy: SlowConsumerPolicy,
+}
+
+impl Default for BrokerSettings {
+    fn default() -> Self {
+        BrokerSettings {
+            queue_capacity: 64,
+            slow_consumer_policy: SlowConsumerPolicy::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct PresenceSettings {
+    /// Remove users from a room once they have been disconnected this long,
+    /// taking their card off the table. Unset keeps them, shown as offline.
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub disconnect_grace_seconds: Option<u64>,
+}
+
 pub fn get_configuration() -> Result<Settings, config::ConfigError> {
     let base_path = std::env::current_dir().expect("Failed to determine the current directory");
     let configuration_directory = base_path.join("configuration");
@@ -42,7 +127,19 @@ pub fn get_configuration() -> Result<Settings, config::ConfigError> {
         )
         .build()?;
 
-    settings.try_deserialize::<Settings>()
+    let settings = settings.try_deserialize::<Settings>()?;
+
+    // A random fallback would sign everyone out on every deploy.
+    if !matches!(environment, Environment::Local)
+        && settings.auth.session_secret.as_deref().is_none_or(str::is_empty)
+    {
+        return Err(config::ConfigError::Message(format!(
+            "auth.session_secret (APP_AUTH__SESSION_SECRET) must be set in the {} environment",
+            environment.as_str()
+        )));
+    }
+
+    Ok(settings)
 }
 
 impl Settings {
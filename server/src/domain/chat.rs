@@ -1,8 +1,11 @@
 use async_graphql::{SimpleObject, InputObject};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, SimpleObject)]
+use crate::{simple_broker::Topic, types::EntityId};
+
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct ChatPosition {
     pub x: f64,
     pub y: f64,
@@ -19,7 +22,7 @@ pub struct ChatPositionInput {
 }
 
 /// A chat message within a room.
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub id: Uuid,
     pub room_id: Uuid,
@@ -39,6 +42,14 @@ pub struct ChatMessage {
     pub timestamp: DateTime<Utc>,
 }
 
+impl Topic for ChatMessage {
+    const SUBSCRIPTION: &'static str = "room_chat";
+
+    fn topic(&self) -> EntityId {
+        self.room_id
+    }
+}
+
 impl ChatMessage {
     pub fn new(
         room_id: Uuid,
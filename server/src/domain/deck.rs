@@ -1,19 +1,404 @@
-use async_graphql::SimpleObject;
+use async_graphql::{ComplexObject, Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::types::{Card, EntityId};
 
+use super::error::RoomError;
+
+pub const MAX_DECK_SIZE: usize = 50;
+pub const MAX_CARD_LENGTH: usize = 16;
+pub const MAX_CARD_COLOR_LENGTH: usize = 32;
+pub const MAX_CARD_EMOJI_LENGTH: usize = 8;
+
+/// A card that stands for something other than an estimate.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecialCard {
+    /// "I need a break" (☕).
+    Break,
+    /// "I don't know" (?).
+    Unknown,
+}
+
+/// A card of a deck. Votes refer to cards by `label`.
+#[derive(Clone, Debug, PartialEq, SimpleObject, Serialize, Deserialize)]
+#[serde(from = "StoredDeckCard")]
+pub struct DeckCard {
+    pub label: Card,
+    /// What the card counts as in statistics; `None` for cards that don't count.
+    pub value: Option<f64>,
+    /// Any CSS color.
+    pub color: Option<String>,
+    pub emoji: Option<String>,
+    pub special: Option<SpecialCard>,
+}
+
+#[derive(Clone, Debug, InputObject)]
+pub struct DeckCardInput {
+    pub label: Card,
+    pub value: Option<f64>,
+    pub color: Option<String>,
+    pub emoji: Option<String>,
+    /// Special cards never count, so they can't have a `value`.
+    pub special: Option<SpecialCard>,
+}
+
+/// Decks used to be stored as plain labels; accept both shapes.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredDeckCard {
+    Label(Card),
+    Card {
+        label: Card,
+        #[serde(default)]
+        value: Option<f64>,
+        #[serde(default)]
+        color: Option<String>,
+        #[serde(default)]
+        emoji: Option<String>,
+        #[serde(default)]
+        special: Option<SpecialCard>,
+    },
+}
+
+impl From<StoredDeckCard> for DeckCard {
+    fn from(stored: StoredDeckCard) -> Self {
+        match stored {
+            StoredDeckCard::Label(label) => DeckCard::from_label(label),
+            StoredDeckCard::Card {
+                label,
+                value,
+                color,
+                emoji,
+                special,
+            } => DeckCard {
+                label,
+                value,
+                color,
+                emoji,
+                special,
+            },
+        }
+    }
+}
+
+impl From<DeckCardInput> for DeckCard {
+    fn from(input: DeckCardInput) -> Self {
+        DeckCard {
+            label: input.label,
+            value: input.value,
+            color: input.color,
+            emoji: input.emoji,
+            special: input.special,
+        }
+    }
+}
+
+impl DeckCard {
+    /// A card with only a label, as older clients send them.
+    ///
+    /// The value and special meaning are inferred from the label; structured
+    /// cards should be used wherever that guess isn't good enough.
+    pub fn from_label(label: Card) -> Self {
+        let special = match label.trim() {
+            "☕" => Some(SpecialCard::Break),
+            "?" => Some(SpecialCard::Unknown),
+            _ => None,
+        };
+
+        DeckCard {
+            value: parse_card_to_number(&label).map(f64::from),
+            label,
+            color: None,
+            emoji: None,
+            special,
+        }
+    }
+
+    fn with_value(label: &str, value: f64) -> Self {
+        DeckCard {
+            label: label.to_string(),
+            value: Some(value),
+            color: None,
+            emoji: None,
+            special: None,
+        }
+    }
+
+    fn with_special(label: &str, special: SpecialCard) -> Self {
+        DeckCard {
+            label: label.to_string(),
+            value: None,
+            color: None,
+            emoji: None,
+            special: Some(special),
+        }
+    }
+}
+
+/// Small helper to coerce common card representations into a numeric f32, or None.
+pub fn parse_card_to_number(s: &str) -> Option<f32> {
+    let s = s.trim();
+
+    if s.is_empty() {
+        return None;
+    }
+
+    if s == "?" || s == "☕" {
+        return None;
+    }
+
+    if s == "½" || s == "1/2" {
+        return Some(0.5);
+    }
+
+    if let Ok(v) = s.parse::<f32>() {
+        return Some(v);
+    }
+
+    None
+}
+
+/// A built-in deck from the server catalog.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeckPreset {
+    Fibonacci,
+    ModifiedFibonacci,
+    TShirt,
+    PowersOfTwo,
+    Hours,
+    FistOfFive,
+}
+
+impl DeckPreset {
+    /// The deck of a room set up without any cards.
+    pub const DEFAULT: DeckPreset = DeckPreset::ModifiedFibonacci;
+
+    pub const ALL: [DeckPreset; 6] = [
+        DeckPreset::Fibonacci,
+        DeckPreset::ModifiedFibonacci,
+        DeckPreset::TShirt,
+        DeckPreset::PowersOfTwo,
+        DeckPreset::Hours,
+        DeckPreset::FistOfFive,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DeckPreset::Fibonacci => "Fibonacci",
+            DeckPreset::ModifiedFibonacci => "Modified Fibonacci",
+            DeckPreset::TShirt => "T-shirt sizes",
+            DeckPreset::PowersOfTwo => "Powers of two",
+            DeckPreset::Hours => "Hours",
+            DeckPreset::FistOfFive => "Fist of five",
+        }
+    }
+
+    pub fn cards(self) -> Vec<DeckCard> {
+        let numbered = |values: &[f64]| -> Vec<DeckCard> {
+            values
+                .iter()
+                .map(|value| DeckCard::with_value(&value.to_string(), *value))
+                .collect()
+        };
+
+        let mut cards = match self {
+            DeckPreset::Fibonacci => numbered(&[0.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 34.0, 55.0, 89.0]),
+            DeckPreset::ModifiedFibonacci => {
+                let mut cards = numbered(&[0.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 20.0, 40.0, 100.0]);
+                cards.insert(1, DeckCard::with_value("½", 0.5));
+                cards
+            }
+            // Sizes count as their Fibonacci equivalents.
+            DeckPreset::TShirt => [("XS", 1.0), ("S", 2.0), ("M", 3.0), ("L", 5.0), ("XL", 8.0), ("XXL", 13.0)]
+                .iter()
+                .map(|(label, value)| DeckCard::with_value(label, *value))
+                .collect(),
+            DeckPreset::PowersOfTwo => numbered(&[0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]),
+            DeckPreset::Hours => numbered(&[0.0, 1.0, 2.0, 4.0, 6.0, 8.0, 12.0, 16.0, 24.0, 32.0, 40.0]),
+            DeckPreset::FistOfFive => return numbered(&[1.0, 2.0, 3.0, 4.0, 5.0]),
+        };
+
+        cards.push(DeckCard::with_special("?", SpecialCard::Unknown));
+        cards.push(DeckCard::with_special("☕", SpecialCard::Break));
+        cards
+    }
+}
+
+/// A catalog entry, as listed by the `decks` query.
 #[derive(Clone, Debug, SimpleObject)]
+pub struct DeckPresetInfo {
+    pub preset: DeckPreset,
+    pub name: String,
+    /// Card labels, as on `Deck.cards`.
+    pub cards: Vec<Card>,
+    pub card_details: Vec<DeckCard>,
+}
+
+impl From<DeckPreset> for DeckPresetInfo {
+    fn from(preset: DeckPreset) -> Self {
+        let card_details = preset.cards();
+        DeckPresetInfo {
+            preset,
+            name: preset.name().to_string(),
+            cards: card_details.iter().map(|card| card.label.clone()).collect(),
+            card_details,
+        }
+    }
+}
+
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+#[graphql(complex)]
 pub struct Deck {
     pub id: EntityId,
-    pub cards: Vec<Card>,
+    /// Exposed as plain labels through `cards` and in full through `cardDetails`.
+    #[graphql(skip)]
+    pub cards: Vec<DeckCard>,
+    /// The catalog deck these cards came from, if any.
+    #[serde(default)]
+    pub preset: Option<DeckPreset>,
 }
 
 impl Deck {
     pub fn new_with_cards(cards: Vec<Card>) -> Self {
         Deck {
             id: Uuid::new_v4(),
-            cards,
+            cards: cards.into_iter().map(DeckCard::from_label).collect(),
+            preset: None,
+        }
+    }
+
+    pub fn set_cards(&mut self, cards: Vec<DeckCard>, preset: Option<DeckPreset>) {
+        self.cards = cards;
+        self.preset = preset;
+    }
+
+    pub fn card(&self, label: &str) -> Option<&DeckCard> {
+        self.cards.iter().find(|card| card.label == label)
+    }
+
+    pub fn contains(&self, label: &str) -> bool {
+        self.card(label).is_some()
+    }
+
+    /// The numeric value of the card labelled `label`, if it has one.
+    pub fn value_of(&self, label: &str) -> Option<f64> {
+        self.card(label).and_then(|card| card.value)
+    }
+
+    pub fn labels(&self) -> Vec<Card> {
+        self.cards.iter().map(|card| card.label.clone()).collect()
+    }
+}
+
+#[ComplexObject]
+impl Deck {
+    /// Card labels, in deck order.
+    async fn cards(&self) -> Vec<Card> {
+        self.labels()
+    }
+
+    async fn card_details(&self) -> &[DeckCard] {
+        &self.cards
+    }
+}
+
+/// Trim `cards` and check they make a usable deck: at least one card, no blanks
+/// or duplicates, no values on special cards, and within the size limits.
+pub fn validate_cards(cards: Vec<DeckCard>) -> Result<Vec<DeckCard>, RoomError> {
+    let trim = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    let cards: Vec<DeckCard> = cards
+        .into_iter()
+        .map(|card| DeckCard {
+            label: card.label.trim().to_string(),
+            color: trim(card.color),
+            emoji: trim(card.emoji),
+            ..card
+        })
+        .collect();
+
+    if cards.is_empty() {
+        return Err(RoomError::InvalidDeck("A deck needs at least one card".to_string()));
+    }
+
+    if cards.len() > MAX_DECK_SIZE {
+        return Err(RoomError::InvalidDeck(format!(
+            "A deck can't have more than {} cards",
+            MAX_DECK_SIZE
+        )));
+    }
+
+    for (index, card) in cards.iter().enumerate() {
+        if card.label.is_empty() {
+            return Err(RoomError::InvalidDeck("Cards can't be blank".to_string()));
+        }
+
+        if card.label.chars().count() > MAX_CARD_LENGTH {
+            return Err(RoomError::InvalidDeck(format!(
+                "Card \"{}\" is longer than {} characters",
+                card.label, MAX_CARD_LENGTH
+            )));
         }
+
+        if cards[..index].iter().any(|other| other.label == card.label) {
+            return Err(RoomError::InvalidDeck(format!(
+                "Card \"{}\" appears more than once",
+                card.label
+            )));
+        }
+
+        if card.value.is_some_and(|value| !value.is_finite()) {
+            return Err(RoomError::InvalidDeck(format!(
+                "Card \"{}\" has an invalid value",
+                card.label
+            )));
+        }
+
+        if card.special.is_some() && card.value.is_some() {
+            return Err(RoomError::InvalidDeck(format!(
+                "Card \"{}\" is special and can't have a value",
+                card.label
+            )));
+        }
+
+        if card.color.as_ref().is_some_and(|c| c.chars().count() > MAX_CARD_COLOR_LENGTH)
+            || card.emoji.as_ref().is_some_and(|e| e.chars().count() > MAX_CARD_EMOJI_LENGTH)
+        {
+            return Err(RoomError::InvalidDeck(format!(
+                "Card \"{}\" has a color or emoji that is too long",
+                card.label
+            )));
+        }
+    }
+
+    Ok(cards)
+}
+
+/// The cards for a deck given at most one of plain `cards` labels, structured
+/// `card_details` or a catalog `preset`. Neither, or an empty `cards` list,
+/// means [`DeckPreset::DEFAULT`].
+pub fn resolve_cards(
+    cards: Option<Vec<Card>>,
+    card_details: Option<Vec<DeckCardInput>>,
+    preset: Option<DeckPreset>,
+) -> Result<(Vec<DeckCard>, Option<DeckPreset>), RoomError> {
+    let cards = cards.filter(|cards| !cards.is_empty());
+    match (cards, card_details, preset) {
+        (None, None, None) => Ok((DeckPreset::DEFAULT.cards(), Some(DeckPreset::DEFAULT))),
+        (Some(cards), None, None) => Ok((
+            validate_cards(cards.into_iter().map(DeckCard::from_label).collect())?,
+            None,
+        )),
+        (None, Some(cards), None) => Ok((
+            validate_cards(cards.into_iter().map(DeckCard::from).collect())?,
+            None,
+        )),
+        (None, None, Some(preset)) => Ok((preset.cards(), Some(preset))),
+        _ => Err(RoomError::InvalidDeck(
+            "Give at most one of cards, cardDetails or a preset".to_string(),
+        )),
     }
 }
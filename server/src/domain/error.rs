@@ -0,0 +1,124 @@
+use async_graphql::{resolver_utils::enum_value, Error, ErrorExtensions};
+
+use crate::types::EntityId;
+
+use super::{
+    permission::{Permission, RoomRole},
+    room::VoteLockPolicy,
+};
+
+/// Errors raised by the room domain.
+///
+/// Converted into GraphQL errors carrying a machine-readable `code` extension,
+/// so clients can react to the kind of failure rather than parse messages.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoomError {
+    RoomNotFound,
+    RoomExists,
+    NotRoomMember,
+    UserNotInRoom(EntityId),
+    Banned,
+    PasscodeRequired,
+    WrongPasscode,
+    Forbidden { permission: Permission, role: RoomRole },
+    OwnerNotTargetable,
+    CountdownDisabled,
+    CountdownRunning,
+    InvalidSetting(String),
+    StoryNotFound(EntityId),
+    InvalidImport(String),
+    InvalidDeck(String),
+    CardNotInDeck(String),
+    RoundNotRevealed,
+    VotesLocked(VoteLockPolicy),
+}
+
+impl RoomError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            RoomError::RoomNotFound => "ROOM_NOT_FOUND",
+            RoomError::RoomExists => "ROOM_EXISTS",
+            RoomError::NotRoomMember => "NOT_ROOM_MEMBER",
+            RoomError::UserNotInRoom(_) => "USER_NOT_IN_ROOM",
+            RoomError::Banned => "BANNED",
+            RoomError::PasscodeRequired => "PASSCODE_REQUIRED",
+            RoomError::WrongPasscode => "WRONG_PASSCODE",
+            RoomError::Forbidden { .. } => "FORBIDDEN",
+            RoomError::OwnerNotTargetable => "OWNER_NOT_TARGETABLE",
+            RoomError::CountdownDisabled => "COUNTDOWN_DISABLED",
+            RoomError::CountdownRunning => "COUNTDOWN_RUNNING",
+            RoomError::InvalidSetting(_) => "INVALID_SETTING",
+            RoomError::StoryNotFound(_) => "STORY_NOT_FOUND",
+            RoomError::InvalidImport(_) => "INVALID_IMPORT",
+            RoomError::InvalidDeck(_) => "INVALID_DECK",
+            RoomError::CardNotInDeck(_) => "INVALID_CARD",
+            RoomError::RoundNotRevealed => "ROUND_NOT_REVEALED",
+            RoomError::VotesLocked(_) => "VOTES_LOCKED",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            RoomError::RoomNotFound => "Room not found".to_string(),
+            RoomError::RoomExists => "A room with this ID already exists".to_string(),
+            RoomError::NotRoomMember => "You are not a member of this room".to_string(),
+            RoomError::UserNotInRoom(uid) => {
+                format!("User with ID {} does not exist in the room", uid)
+            }
+            RoomError::Banned => "User is banned from this room".to_string(),
+            RoomError::PasscodeRequired => "This room requires a passcode".to_string(),
+            RoomError::WrongPasscode => "Incorrect passcode".to_string(),
+            RoomError::Forbidden { permission, .. } => match permission {
+                Permission::Moderate => "Only the room owner can do that".to_string(),
+                Permission::Vote => "Spectators can't vote".to_string(),
+                Permission::Chat => "You can't chat in this room".to_string(),
+            },
+            RoomError::OwnerNotTargetable => {
+                "The room owner can't be kicked or banned".to_string()
+            }
+            RoomError::CountdownDisabled => {
+                "Countdown reveal is disabled for this room".to_string()
+            }
+            RoomError::CountdownRunning => {
+                "A countdown is already running in this room".to_string()
+            }
+            RoomError::InvalidSetting(reason) => reason.clone(),
+            RoomError::StoryNotFound(id) => format!("Story with ID {} does not exist in the room", id),
+            RoomError::InvalidImport(reason) => reason.clone(),
+            RoomError::InvalidDeck(reason) => reason.clone(),
+            RoomError::CardNotInDeck(card) => format!("\"{}\" is not a card in this room's deck", card),
+            RoomError::RoundNotRevealed => "The current round hasn't been revealed yet".to_string(),
+            RoomError::VotesLocked(policy) => match policy {
+                VoteLockPolicy::AtCountdown => {
+                    "Votes are locked once the countdown starts".to_string()
+                }
+                _ => "Votes are locked once the cards are revealed".to_string(),
+            },
+        }
+    }
+}
+
+impl ErrorExtensions for RoomError {
+    fn extend(&self) -> Error {
+        Error::new(self.message()).extend_with(|_, e| {
+            e.set("code", self.code());
+
+            if let RoomError::Forbidden { permission, role } = self {
+                e.set("permission", enum_value(*permission));
+                e.set("role", enum_value(*role));
+            }
+
+            if let RoomError::VotesLocked(policy) = self {
+                e.set("voteLock", enum_value(*policy));
+            }
+        })
+    }
+}
+
+// `RoomError` deliberately doesn't implement `Display`, so this takes the place of
+// async-graphql's blanket conversion and `?` keeps the error code.
+impl From<RoomError> for Error {
+    fn from(err: RoomError) -> Self {
+        err.extend()
+    }
+}
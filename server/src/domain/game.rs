@@ -1,15 +1,21 @@
 use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::types::{Card, EntityId};
 
-#[derive(Clone, Debug, SimpleObject)]
+use super::statistics::VoteStatistics;
+
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct Game {
     pub id: EntityId,
     pub table: Vec<UserCard>,
+    /// Computed when the cards are revealed; `None` until then.
+    #[serde(default)]
+    pub statistics: Option<VoteStatistics>,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct UserCard {
     pub user_id: EntityId,
     pub card: Option<Card>,
@@ -20,10 +26,17 @@ impl Game {
         Game {
             id: Uuid::new_v4(),
             table: vec![],
+            statistics: None,
         }
     }
 }
 
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UserCard {
     pub fn new(user_id: EntityId, card: Card) -> Self {
         UserCard {
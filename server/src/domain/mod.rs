@@ -1,5 +1,14 @@
 pub mod deck;
+pub mod error;
 pub mod game;
+pub mod passcode;
+pub mod permission;
 pub mod room;
+pub mod room_change;
+pub mod room_event;
+pub mod round;
+pub mod statistics;
+pub mod story;
+pub mod story_import;
 pub mod user;
 pub mod chat;
@@ -0,0 +1,40 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use uuid::Uuid;
+
+use super::error::RoomError;
+
+pub const MIN_PASSCODE_LENGTH: usize = 4;
+pub const MAX_PASSCODE_LENGTH: usize = 64;
+
+/// Hash a room passcode for storage, as an Argon2id PHC string.
+pub fn hash_passcode(passcode: &str) -> Result<String, RoomError> {
+    let length = passcode.chars().count();
+    if !(MIN_PASSCODE_LENGTH..=MAX_PASSCODE_LENGTH).contains(&length) {
+        return Err(RoomError::InvalidSetting(format!(
+            "Passcode must be between {} and {} characters",
+            MIN_PASSCODE_LENGTH, MAX_PASSCODE_LENGTH
+        )));
+    }
+
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())
+        .expect("a UUID is a valid salt length");
+
+    Argon2::default()
+        .hash_password(passcode.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| RoomError::InvalidSetting(format!("Could not hash passcode: {}", err)))
+}
+
+/// Whether `passcode` matches a hash produced by [`hash_passcode`].
+pub fn verify_passcode(hash: &str, passcode: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(passcode.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,34 @@
+use async_graphql::Enum;
+use serde::{Deserialize, Serialize};
+
+/// What a user is allowed to do in a room.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomRole {
+    /// Runs the session: moderates users, controls rounds and room settings.
+    Owner,
+    /// Votes and chats.
+    Participant,
+    /// Watches and chats, but doesn't vote.
+    Spectator,
+}
+
+/// An action that requires a certain role.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Chat and mark chat as read.
+    Chat,
+    /// Pick a card.
+    Vote,
+    /// Kick/ban users, reveal and reset rounds, change the deck and room settings.
+    Moderate,
+}
+
+impl RoomRole {
+    pub fn can(self, permission: Permission) -> bool {
+        match permission {
+            Permission::Chat => true,
+            Permission::Vote => matches!(self, RoomRole::Owner | RoomRole::Participant),
+            Permission::Moderate => self == RoomRole::Owner,
+        }
+    }
+}
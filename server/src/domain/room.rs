@@ -1,17 +1,62 @@
-use async_graphql::SimpleObject;
+use std::collections::HashMap;
+
+use async_graphql::{Enum, SimpleObject};
 use uuid::Uuid;
 use chrono::{DateTime, Duration, Utc};
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
 
-use crate::types::{Card, EntityId};
+use crate::{
+    simple_broker::Topic,
+    types::{Card, EntityId},
+};
 
 use super::{
-    deck::Deck,
+    deck::{Deck, DeckCard, DeckPreset},
+    error::RoomError,
     game::{Game, UserCard},
+    permission::{Permission, RoomRole},
+    round::{Round, DEFAULT_ROUNDS_PAGE_SIZE, MAX_ROUNDS_PAGE_SIZE, MAX_ROUND_HISTORY},
+    statistics::VoteStatistics,
+    story::{Story, StoryStatus, MAX_STORIES},
+    story_import::StoryImportError,
     user::User,
 };
 
-#[derive(Clone, Debug, SimpleObject)]
+pub const DEFAULT_COUNTDOWN_SECONDS: i32 = 3;
+pub const MIN_COUNTDOWN_SECONDS: i32 = 3;
+pub const MAX_COUNTDOWN_SECONDS: i32 = 10;
+
+pub const MIN_AUTO_RESET_SECONDS: i32 = 1;
+pub const MAX_AUTO_RESET_SECONDS: i32 = 600;
+
+fn default_countdown_seconds() -> i32 {
+    DEFAULT_COUNTDOWN_SECONDS
+}
+
+/// When a round stops accepting vote changes.
+#[derive(Enum, Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteLockPolicy {
+    /// Votes can change at any time, even after the reveal.
+    Never,
+    /// Votes freeze as soon as the reveal countdown starts.
+    AtCountdown,
+    /// Votes can change during the countdown but not after the reveal.
+    #[default]
+    AtReveal,
+}
+
+/// Where the current round is on its way to a reveal.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevealStage {
+    Idle,
+    Countdown,
+    Revealed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 #[graphql(complex)]
 pub struct Room {
     pub id: EntityId,
@@ -23,16 +68,101 @@ pub struct Room {
     pub is_game_over: bool,
     pub room_owner_id: Option<EntityId>,
     pub countdown_enabled: bool,
-    pub reveal_stage: Option<String>,
+    pub reveal_stage: Option<RevealStage>,
     pub countdown_value: Option<i32>,
     pub confirm_new_game: bool,
+
+    /// Length of the reveal countdown, in seconds.
+    #[serde(default = "default_countdown_seconds")]
+    pub countdown_seconds: i32,
+
+    /// Reveal automatically once every user in the room has voted.
+    #[serde(default)]
+    pub auto_reveal: bool,
+
+    /// Start a new round this many seconds after a reveal; `None` disables it.
+    #[serde(default)]
+    pub auto_reset_seconds: Option<i32>,
+
+    /// When votes stop being accepted.
+    #[serde(default)]
+    pub vote_lock: VoteLockPolicy,
+
     pub chat_history: Vec<crate::domain::chat::ChatMessage>,
 
+    /// Title of the story being estimated in the current round.
+    #[serde(default)]
+    pub round_title: Option<String>,
+
+    /// Stories to estimate, in backlog order.
+    #[serde(default)]
+    pub stories: Vec<Story>,
+
+    /// The story being voted on.
+    #[serde(default)]
+    pub current_story_id: Option<EntityId>,
+
+    /// Revealed rounds, oldest first. Exposed through the paginated `rounds` field.
     #[graphql(skip)]
-    pub last_active: DateTime<Utc>,
+    #[serde(default)]
+    pub round_history: Vec<Round>,
 
+    /// Who started the running countdown, credited with the reveal.
     #[graphql(skip)]
-    pub last_active_instant: Instant
+    #[serde(default)]
+    pub countdown_started_by: Option<EntityId>,
+
+    /// Argon2 hash of the room passcode; `None` means anyone with the ID can join.
+    #[graphql(skip)]
+    #[serde(default)]
+    pub passcode_hash: Option<String>,
+
+    /// Identifies the running countdown so a stale timer can't act on a newer one.
+    #[graphql(skip)]
+    #[serde(default)]
+    pub countdown_id: Option<Uuid>,
+
+    #[graphql(skip)]
+    pub last_active: DateTime<Utc>,
+
+    /// Bumped by one on every change; see `RoomChange`.
+    #[serde(default)]
+    pub version: u64,
+}
+
+/// What listings show about a room: no members, chat or cards.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RoomSummary {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub room_owner_id: Option<EntityId>,
+    pub user_count: usize,
+    pub has_passcode: bool,
+    pub is_game_over: bool,
+    pub last_active: DateTime<Utc>,
+}
+
+impl From<&Room> for RoomSummary {
+    fn from(room: &Room) -> Self {
+        RoomSummary {
+            id: room.id,
+            name: room.name.clone(),
+            room_owner_id: room.room_owner_id,
+            user_count: room.users.len(),
+            has_passcode: room.passcode_hash.is_some(),
+            is_game_over: room.is_game_over,
+            last_active: room.last_active,
+        }
+    }
+}
+
+impl Topic for Room {
+    const SUBSCRIPTION: &'static str = "room";
+    const SNAPSHOT: bool = true;
+
+    fn topic(&self) -> EntityId {
+        self.id
+    }
 }
 
 impl Room {
@@ -47,12 +177,23 @@ impl Room {
             is_game_over: false,
             room_owner_id: None,
             countdown_enabled: false,
-            reveal_stage: Some("idle".to_string()),
+            reveal_stage: Some(RevealStage::Idle),
             countdown_value: None,
             confirm_new_game: true,
+            countdown_seconds: DEFAULT_COUNTDOWN_SECONDS,
+            auto_reveal: false,
+            auto_reset_seconds: None,
+            vote_lock: VoteLockPolicy::default(),
             last_active: Utc::now(),
-            last_active_instant: Instant::now(),
             chat_history: Vec::new(),
+            countdown_id: None,
+            passcode_hash: None,
+            round_title: None,
+            round_history: Vec::new(),
+            stories: Vec::new(),
+            current_story_id: None,
+            countdown_started_by: None,
+            version: 0,
         }
     }
 
@@ -104,11 +245,117 @@ impl Room {
         self.game.table.retain(|uc| uc.user_id != user_id);
     }
 
-    pub fn set_room_owner(&mut self, user_id: Option<EntityId>) -> Result<(), String> {
+    /// Record whether `user_id` is connected; returns whether anything changed.
+    pub fn set_online(&mut self, user_id: EntityId, online: bool) -> bool {
+        match self.users.iter_mut().find(|user| user.id == user_id) {
+            Some(user) if user.online != online => {
+                user.online = online;
+                user.last_seen = Some(Utc::now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // === Permissions ===
+    // Mutators acting for a user take them as `actor` (an `Option` where the
+    // server also acts on its own) and check their permission before changing anything.
+    pub fn role_of(&self, user_id: EntityId) -> Option<RoomRole> {
+        if !self.is_user_exist(user_id) {
+            return None;
+        }
+
+        if self.room_owner_id == Some(user_id) {
+            Some(RoomRole::Owner)
+        } else if self.is_spectator(user_id) {
+            Some(RoomRole::Spectator)
+        } else {
+            Some(RoomRole::Participant)
+        }
+    }
+
+    /// Check that `user_id` may perform `permission`, returning their role.
+    pub fn authorize(&self, user_id: EntityId, permission: Permission) -> Result<RoomRole, RoomError> {
+        let role = self.role_of(user_id).ok_or(RoomError::NotRoomMember)?;
+
+        // An owner who only watches keeps moderating, but doesn't vote.
+        let spectating = permission == Permission::Vote && self.is_spectator(user_id);
+
+        if !role.can(permission) || spectating {
+            return Err(RoomError::Forbidden { permission, role });
+        }
+
+        Ok(role)
+    }
+
+    pub fn is_spectator(&self, user_id: EntityId) -> bool {
+        self.users.iter().any(|user| user.id == user_id && user.is_spectator)
+    }
+
+    /// Switch `user_id` between spectating and voting. Anyone can switch
+    /// themselves; switching someone else takes the owner. Becoming a
+    /// spectator takes their card off the table while the round is open; once
+    /// revealed or locked, the card stays as it was played.
+    pub fn set_spectator(
+        &mut self,
+        actor: EntityId,
+        user_id: EntityId,
+        spectator: bool,
+    ) -> Result<(), RoomError> {
+        if actor == user_id {
+            self.authorize(actor, Permission::Chat)?;
+        } else {
+            self.authorize(actor, Permission::Moderate)?;
+        }
+
+        let withdraw_vote = spectator && !self.is_game_over && self.ensure_votes_open().is_ok();
+
+        let user = self
+            .users
+            .iter_mut()
+            .find(|user| user.id == user_id)
+            .ok_or(RoomError::UserNotInRoom(user_id))?;
+
+        user.is_spectator = spectator;
+
+        if withdraw_vote {
+            user.last_card_picked = None;
+            user.last_card_value = None;
+            self.game.table.retain(|uc| uc.user_id != user_id);
+        }
+
+        Ok(())
+    }
+
+    /// Hand the room to `new_owner` on behalf of `actor`.
+    ///
+    /// The owner can pass the room on (or give it up); an ownerless room can be
+    /// claimed by any member, but only for themselves.
+    pub fn transfer_ownership(
+        &mut self,
+        actor: EntityId,
+        new_owner: Option<EntityId>,
+    ) -> Result<(), RoomError> {
+        if self.room_owner_id.is_some() {
+            self.authorize(actor, Permission::Moderate)?;
+        } else {
+            let role = self.authorize(actor, Permission::Chat)?;
+            if new_owner != Some(actor) {
+                return Err(RoomError::Forbidden {
+                    permission: Permission::Moderate,
+                    role,
+                });
+            }
+        }
+
+        self.set_room_owner(new_owner)
+    }
+
+    pub fn set_room_owner(&mut self, user_id: Option<EntityId>) -> Result<(), RoomError> {
         match user_id {
             Some(uid) => {
                 if !self.is_user_exist(uid) {
-                    return Err(format!("User with ID {} does not exist in the room", uid));
+                    return Err(RoomError::UserNotInRoom(uid));
                 }
                 self.room_owner_id = Some(uid);
             }
@@ -119,39 +366,108 @@ impl Room {
         Ok(())
     }
 
-    pub fn rename(&mut self, new_name: Option<String>) {
+    pub fn rename(&mut self, actor: EntityId, new_name: Option<String>) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
         self.name = new_name;
+        Ok(())
+    }
+
+    pub fn set_deck(
+        &mut self,
+        actor: EntityId,
+        cards: Vec<DeckCard>,
+        preset: Option<DeckPreset>,
+    ) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.deck.set_cards(cards, preset);
+        Ok(())
     }
 
     pub fn is_banned(&self, user_id: EntityId) -> bool {
         self.banned_users.contains(&user_id)
     }
 
-    pub fn kick_user(&mut self, user_id: EntityId) {
+    pub fn kick_user(&mut self, actor: EntityId, user_id: EntityId) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        if self.room_owner_id == Some(user_id) {
+            return Err(RoomError::OwnerNotTargetable);
+        }
         self.remove_user(user_id);
+        Ok(())
     }
 
-    pub fn ban_user(&mut self, user_id: EntityId) {
+    pub fn ban_user(&mut self, actor: EntityId, user_id: EntityId) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        if self.room_owner_id == Some(user_id) {
+            return Err(RoomError::OwnerNotTargetable);
+        }
         self.remove_user(user_id);
         if !self.is_banned(user_id) {
             self.banned_users.push(user_id);
         }
+        Ok(())
     }
 
-    pub fn unban_user(&mut self, user_id: EntityId) {
+    pub fn unban_user(&mut self, actor: EntityId, user_id: EntityId) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
         self.banned_users.retain(|id| *id != user_id);
+        Ok(())
+    }
+
+    // === Passcode ===
+    pub fn set_passcode_hash(
+        &mut self,
+        actor: EntityId,
+        passcode_hash: Option<String>,
+    ) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.passcode_hash = passcode_hash;
+        Ok(())
+    }
+
+    /// Whether `user_id` has to present the passcode to get in; members rejoin freely.
+    pub fn requires_passcode(&self, user_id: EntityId) -> bool {
+        self.passcode_hash.is_some() && !self.is_user_exist(user_id)
     }
 
     // === Countdown management ===
-    pub fn enable_countdown(&mut self, enabled: bool) {
+    pub fn enable_countdown(&mut self, actor: EntityId, enabled: bool) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
         self.countdown_enabled = enabled;
+        Ok(())
     }
 
-    pub fn start_countdown(&mut self) {
-        if self.countdown_enabled {
-            self.reveal_stage = Some("countdown".to_string());
-            self.countdown_value = Some(3);
+    /// Start a countdown and return its ID; fails if one is already running.
+    /// `started_by` is `None` when the last vote started it.
+    pub fn start_countdown(&mut self, started_by: Option<EntityId>) -> Result<Uuid, RoomError> {
+        if let Some(actor) = started_by {
+            self.authorize(actor, Permission::Moderate)?;
+        }
+
+        if !self.countdown_enabled {
+            return Err(RoomError::CountdownDisabled);
+        }
+
+        if self.is_countdown_running() {
+            return Err(RoomError::CountdownRunning);
         }
+
+        let countdown_id = Uuid::new_v4();
+        self.reveal_stage = Some(RevealStage::Countdown);
+        self.countdown_value = Some(self.countdown_seconds);
+        self.countdown_id = Some(countdown_id);
+        self.countdown_started_by = started_by;
+
+        Ok(countdown_id)
+    }
+
+    pub fn is_countdown_running(&self) -> bool {
+        self.reveal_stage == Some(RevealStage::Countdown)
+    }
+
+    /// Whether `countdown_id` is the countdown currently running.
+    pub fn is_current_countdown(&self, countdown_id: Uuid) -> bool {
+        self.is_countdown_running() && self.countdown_id == Some(countdown_id)
     }
 
     pub fn update_countdown_value(&mut self, value: i32) {
@@ -159,40 +475,476 @@ impl Room {
     }
 
     pub fn complete_countdown(&mut self) {
-        self.reveal_stage = Some("revealed".to_string());
+        // Whoever started the countdown was allowed to reveal back then.
+        self.reveal_cards(self.countdown_started_by);
+    }
+
+    /// Stop the countdown; `cancelled_by` is `None` when the server drops it.
+    pub fn cancel_countdown(&mut self, cancelled_by: Option<EntityId>) -> Result<(), RoomError> {
+        if let Some(actor) = cancelled_by {
+            self.authorize(actor, Permission::Moderate)?;
+        }
+
+        self.reveal_stage = Some(RevealStage::Cancelled);
         self.countdown_value = None;
+        self.countdown_id = None;
+        self.countdown_started_by = None;
+        Ok(())
+    }
+
+    pub fn toggle_confirm_new_game(&mut self, actor: EntityId, enabled: bool) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.confirm_new_game = enabled;
+        Ok(())
+    }
+
+    // === Round management ===
+    /// Reveal the cards; `revealed_by` is `None` when the last vote revealed them.
+    pub fn reveal(&mut self, revealed_by: Option<EntityId>) -> Result<(), RoomError> {
+        if let Some(actor) = revealed_by {
+            self.authorize(actor, Permission::Moderate)?;
+        }
+
+        self.reveal_cards(revealed_by);
+        Ok(())
+    }
+
+    fn reveal_cards(&mut self, revealed_by: Option<EntityId>) {
         self.is_game_over = true;
+        self.reveal_stage = Some(RevealStage::Revealed);
+        self.countdown_value = None;
+        self.countdown_id = None;
+        self.countdown_started_by = None;
+
+        self.refresh_statistics();
+        self.archive_round(revealed_by);
     }
 
-    pub fn cancel_countdown(&mut self) {
-        self.reveal_stage = Some("cancelled".to_string());
+    /// Start a new round; `reset_by` is `None` for the automatic reset.
+    pub fn reset_game(&mut self, reset_by: Option<EntityId>) -> Result<(), RoomError> {
+        if let Some(actor) = reset_by {
+            self.authorize(actor, Permission::Moderate)?;
+        }
+
+        // Move on once the current story has been revealed at least once.
+        if self.current_round_mut().is_some() {
+            self.advance_story();
+        }
+
+        self.is_game_over = false;
+        self.game = Game::new();
+        self.reveal_stage = Some(RevealStage::Idle);
         self.countdown_value = None;
+        self.countdown_id = None;
+        self.countdown_started_by = None;
+        self.round_title = None;
+
+        for u in self.users.iter_mut() {
+            u.last_card_picked = None;
+            u.last_card_value = None;
+        }
+        Ok(())
     }
 
-    pub fn toggle_confirm_new_game(&mut self, enabled: bool) {
-        self.confirm_new_game = enabled;
+    pub fn set_round_title(&mut self, actor: EntityId, title: Option<String>) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.round_title = title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        Ok(())
+    }
+
+    /// The archived entry for the game on the table, if it has been revealed.
+    fn current_round_mut(&mut self) -> Option<&mut Round> {
+        let game_id = self.game.id;
+        self.round_history.last_mut().filter(|round| round.game_id == game_id)
+    }
+
+    fn archive_round(&mut self, revealed_by: Option<EntityId>) {
+        // Revealing an already revealed game just refreshes its entry.
+        if self.current_round_mut().is_some() {
+            self.sync_current_round();
+            return;
+        }
+
+        let number = self.round_history.last().map_or(1, |round| round.number + 1);
+        let story = self.current_story();
+        self.round_history.push(Round {
+            number,
+            game_id: self.game.id,
+            story_id: story.map(|story| story.id),
+            story_title: story.map(|story| story.title.clone()).or_else(|| self.round_title.clone()),
+            votes: self.game.table.clone(),
+            statistics: self.game.statistics.clone(),
+            revealed_at: Utc::now(),
+            revealed_by,
+            final_estimate: None,
+            final_estimate_at: None,
+            final_estimate_by: None,
+        });
+
+        if self.round_history.len() > MAX_ROUND_HISTORY {
+            self.round_history.drain(0..self.round_history.len() - MAX_ROUND_HISTORY);
+        }
+    }
+
+    fn refresh_statistics(&mut self) {
+        self.game.statistics = Some(VoteStatistics::compute(&self.game.table, &self.deck));
+    }
+
+    /// Keep the statistics and the archived round in step with votes changed after the reveal.
+    pub fn sync_current_round(&mut self) {
+        self.refresh_statistics();
+
+        let votes = self.game.table.clone();
+        let statistics = self.game.statistics.clone();
+        if let Some(round) = self.current_round_mut() {
+            round.votes = votes;
+            round.statistics = statistics;
+        }
+    }
+
+    /// Record the agreed estimate for the revealed round (and its story); `None` clears it.
+    pub fn set_final_estimate(
+        &mut self,
+        card: Option<Card>,
+        set_by: EntityId,
+    ) -> Result<(), RoomError> {
+        self.authorize(set_by, Permission::Moderate)?;
+
+        if let Some(card) = &card
+            && !self.deck.contains(card)
+        {
+            return Err(RoomError::CardNotInDeck(card.clone()));
+        }
+
+        let round = self.current_round_mut().ok_or(RoomError::RoundNotRevealed)?;
+        round.final_estimate = card.clone();
+        round.final_estimate_at = card.as_ref().map(|_| Utc::now());
+        round.final_estimate_by = card.as_ref().map(|_| set_by);
+
+        // The round remembers which story it was, even if the selection has moved since.
+        if let Some(story_id) = round.story_id
+            && let Ok(story) = self.story_mut(story_id)
+        {
+            story.final_estimate = card;
+        }
+
+        Ok(())
+    }
+
+    // === Story backlog ===
+    pub fn current_story(&self) -> Option<&Story> {
+        let id = self.current_story_id?;
+        self.stories.iter().find(|story| story.id == id)
+    }
+
+    pub fn story_mut(&mut self, story_id: EntityId) -> Result<&mut Story, RoomError> {
+        self.stories
+            .iter_mut()
+            .find(|story| story.id == story_id)
+            .ok_or(RoomError::StoryNotFound(story_id))
+    }
+
+    pub fn add_story(&mut self, actor: EntityId, story: Story) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.push_story(story)
+    }
+
+    /// Add parsed stories to the backlog, returning how many fit and the rows that didn't.
+    pub fn import_stories(
+        &mut self,
+        actor: EntityId,
+        stories: Vec<(usize, Story)>,
+    ) -> Result<(usize, Vec<StoryImportError>), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+
+        let mut imported_count = 0;
+        let mut errors = Vec::new();
+        for (row, story) in stories {
+            match self.push_story(story) {
+                Ok(()) => imported_count += 1,
+                Err(err) => errors.push(StoryImportError {
+                    row,
+                    message: err.message(),
+                }),
+            }
+        }
+        Ok((imported_count, errors))
+    }
+
+    /// Apply the given changes to a story; `None` leaves a field as it is.
+    /// Setting it `Voting` selects it, as [`Room::select_story`] does, and any
+    /// other status takes the current story out of the selection.
+    pub fn update_story(
+        &mut self,
+        actor: EntityId,
+        story_id: EntityId,
+        title: Option<String>,
+        description: Option<Option<String>>,
+        external_key: Option<Option<String>>,
+        status: Option<StoryStatus>,
+    ) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+
+        let story = self.story_mut(story_id)?;
+        if let Some(title) = title {
+            story.title = title;
+        }
+        if let Some(description) = description {
+            story.description = description;
+        }
+        if let Some(external_key) = external_key {
+            story.external_key = external_key;
+        }
+
+        match status {
+            Some(StoryStatus::Voting) => self.choose_story(Some(story_id))?,
+            Some(status) => {
+                self.story_mut(story_id)?.status = status;
+                if self.current_story_id == Some(story_id) {
+                    self.current_story_id = None;
+                }
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn push_story(&mut self, story: Story) -> Result<(), RoomError> {
+        if self.stories.len() >= MAX_STORIES {
+            return Err(RoomError::InvalidSetting(format!(
+                "A room can't hold more than {} stories",
+                MAX_STORIES
+            )));
+        }
+        self.stories.push(story);
+        Ok(())
+    }
+
+    pub fn delete_story(&mut self, actor: EntityId, story_id: EntityId) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.story_mut(story_id)?;
+        self.stories.retain(|story| story.id != story_id);
+
+        if self.current_story_id == Some(story_id) {
+            self.current_story_id = None;
+        }
+        Ok(())
+    }
+
+    /// Put the backlog in the order of `story_ids`, which must list every story once.
+    pub fn reorder_stories(&mut self, actor: EntityId, story_ids: &[EntityId]) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+
+        let mut positions = HashMap::with_capacity(story_ids.len());
+        for (position, id) in story_ids.iter().enumerate() {
+            self.stories
+                .iter()
+                .find(|story| story.id == *id)
+                .ok_or(RoomError::StoryNotFound(*id))?;
+            positions.insert(*id, position);
+        }
+
+        if positions.len() != story_ids.len() || positions.len() != self.stories.len() {
+            return Err(RoomError::InvalidSetting(
+                "The new order must list every story exactly once".to_string(),
+            ));
+        }
+
+        self.stories.sort_by_key(|story| positions[&story.id]);
+        Ok(())
+    }
+
+    /// Make `story_id` the story being voted on; `None` clears the selection.
+    pub fn select_story(&mut self, actor: EntityId, story_id: Option<EntityId>) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.choose_story(story_id)
+    }
+
+    fn choose_story(&mut self, story_id: Option<EntityId>) -> Result<(), RoomError> {
+        if let Some(id) = story_id {
+            self.story_mut(id)?;
+        }
+
+        if let Some(previous) = self.current_story_id
+            && let Ok(story) = self.story_mut(previous)
+            && story.status == StoryStatus::Voting
+        {
+            story.status = StoryStatus::Pending;
+        }
+
+        if let Some(id) = story_id {
+            self.story_mut(id)?.status = StoryStatus::Voting;
+        }
+        self.current_story_id = story_id;
+        Ok(())
+    }
+
+    /// Mark the story of the revealed round estimated and, unless another
+    /// story has been selected since, select the next pending one.
+    fn advance_story(&mut self) {
+        let Some(revealed) = self.current_round_mut().and_then(|round| round.story_id) else {
+            return;
+        };
+
+        // Selecting another story after the reveal put this one back to pending.
+        if let Ok(story) = self.story_mut(revealed)
+            && matches!(story.status, StoryStatus::Voting | StoryStatus::Pending)
+        {
+            story.status = StoryStatus::Estimated;
+        }
+
+        if self.current_story_id != Some(revealed) {
+            return;
+        }
+
+        let position = self.stories.iter().position(|story| story.id == revealed);
+        let next = self
+            .stories
+            .iter()
+            .skip(position.map_or(0, |p| p + 1))
+            .chain(self.stories.iter())
+            .find(|story| story.status == StoryStatus::Pending)
+            .map(|story| story.id);
+
+        // `next` is known to exist, so this can't fail.
+        let _ = self.choose_story(next);
+    }
+
+    // === Voting ===
+    /// Fails with [`RoomError::VotesLocked`] if the vote lock policy forbids voting now.
+    pub fn ensure_votes_open(&self) -> Result<(), RoomError> {
+        let locked = match self.vote_lock {
+            VoteLockPolicy::Never => false,
+            VoteLockPolicy::AtCountdown => self.is_game_over || self.is_countdown_running(),
+            VoteLockPolicy::AtReveal => self.is_game_over,
+        };
+
+        if locked {
+            Err(RoomError::VotesLocked(self.vote_lock))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Put `card` on the table for `user_id`; an empty card withdraws their vote.
+    pub fn cast_vote(&mut self, user_id: EntityId, card: &str) -> Result<(), RoomError> {
+        self.authorize(user_id, Permission::Vote)?;
+
+        let card = card.trim();
+        if !card.is_empty() && !self.deck.contains(card) {
+            return Err(RoomError::CardNotInDeck(card.to_string()));
+        }
+
+        self.ensure_votes_open()?;
+
+        self.game.table.retain(|uc| uc.user_id != user_id);
+
+        if let Some(user) = self.users.iter_mut().find(|u| u.id == user_id) {
+            if card.is_empty() {
+                user.last_card_picked = None;
+                user.last_card_value = None;
+            } else {
+                user.last_card_picked = Some(card.to_string());
+                user.last_card_value = self.deck.value_of(card).map(|value| value as f32);
+            }
+        }
+
+        if !card.is_empty() {
+            self.game.table.push(UserCard::new(user_id, card.to_string()));
+        }
+
+        if self.is_game_over {
+            self.sync_current_round();
+        }
+
+        Ok(())
+    }
+
+    /// Whether every voter (spectators don't count) has a card on the table.
+    pub fn everyone_voted(&self) -> bool {
+        let mut voters = self.users.iter().filter(|user| !user.is_spectator).peekable();
+
+        voters.peek().is_some()
+            && voters.all(|user| self.game.table.iter().any(|uc| uc.user_id == user.id))
+    }
+
+    /// Whether the last vote should trigger an automatic reveal.
+    pub fn should_auto_reveal(&self) -> bool {
+        self.auto_reveal && !self.is_game_over && !self.is_countdown_running() && self.everyone_voted()
+    }
+
+    // === Settings ===
+    pub fn set_countdown_seconds(&mut self, actor: EntityId, seconds: i32) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        if !(MIN_COUNTDOWN_SECONDS..=MAX_COUNTDOWN_SECONDS).contains(&seconds) {
+            return Err(RoomError::InvalidSetting(format!(
+                "Countdown must be between {} and {} seconds",
+                MIN_COUNTDOWN_SECONDS, MAX_COUNTDOWN_SECONDS
+            )));
+        }
+        self.countdown_seconds = seconds;
+        Ok(())
+    }
+
+    pub fn set_auto_reveal(&mut self, actor: EntityId, enabled: bool) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.auto_reveal = enabled;
+        Ok(())
+    }
+
+    pub fn set_vote_lock(&mut self, actor: EntityId, policy: VoteLockPolicy) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        self.vote_lock = policy;
+        Ok(())
+    }
+
+    pub fn set_auto_reset_seconds(&mut self, actor: EntityId, seconds: Option<i32>) -> Result<(), RoomError> {
+        self.authorize(actor, Permission::Moderate)?;
+        if let Some(seconds) = seconds
+            && !(MIN_AUTO_RESET_SECONDS..=MAX_AUTO_RESET_SECONDS).contains(&seconds)
+        {
+            return Err(RoomError::InvalidSetting(format!(
+                "Auto-reset delay must be between {} and {} seconds",
+                MIN_AUTO_RESET_SECONDS, MAX_AUTO_RESET_SECONDS
+            )));
+        }
+        self.auto_reset_seconds = seconds;
+        Ok(())
     }
 
     // === Activity / cleanup helpers ===
+    /// Record a change: the room counts as active and moves to the next version.
     pub fn touch(&mut self) {
         self.last_active = Utc::now();
-        self.last_active_instant = Instant::now();
+        self.bump_version();
+    }
+
+    /// Move to the next version without counting as activity, for changes
+    /// nobody made on purpose (a user's connection coming or going, old chat
+    /// being pruned).
+    pub fn bump_version(&mut self) {
+        self.version += 1;
     }
 
     pub fn is_safe_to_remove(&self) -> bool {
-        self.reveal_stage.as_deref() != Some("countdown")
+        !self.is_countdown_running()
     }
 
     pub fn is_inactive(&self, ttl: std::time::Duration) -> bool {
-        self.last_active_instant.elapsed() > ttl
+        // Wall-clock based so inactivity survives a restart of the server.
+        let ttl = Duration::from_std(ttl).unwrap_or(Duration::MAX);
+        Utc::now().signed_duration_since(self.last_active) > ttl
     }
 
     // === Chat functions ===
-    pub fn push_chat(&mut self, msg: crate::domain::chat::ChatMessage) {
+    /// Post `msg` as its sender, who must be in the room.
+    pub fn push_chat(&mut self, msg: crate::domain::chat::ChatMessage) -> Result<(), RoomError> {
+        self.authorize(msg.user_id, Permission::Chat)?;
         self.chat_history.push(msg);
         if self.chat_history.len() > 100 {
             self.chat_history.drain(0..self.chat_history.len() - 100);
         }
+        Ok(())
     }
 
     pub fn prune_chat_history(&mut self, max_age: Duration) -> usize {
@@ -219,15 +971,18 @@ impl Room {
         }
     }
 
-    pub fn mark_chat_seen(&mut self, user_id: EntityId) {
+    pub fn mark_chat_seen(&mut self, user_id: EntityId) -> Result<(), RoomError> {
+        self.authorize(user_id, Permission::Chat)?;
+
         let latest_id = match self.chat_history.last() {
             Some(msg) => msg.id,
-            None => return,
+            None => return Ok(()),
         };
 
         if let Some(user) = self.users.iter_mut().find(|u| u.id == user_id) {
             user.last_seen_chat_message_id = Some(latest_id);
         }
+        Ok(())
     }
 }
 
@@ -239,4 +994,157 @@ impl Room {
     ) -> Option<bool> {
         Some(self.has_unread_chat_internal(user_id))
     }
+
+    /// Revealed rounds, newest first.
+    async fn rounds(&self, offset: Option<usize>, limit: Option<usize>) -> Vec<Round> {
+        let limit = limit.unwrap_or(DEFAULT_ROUNDS_PAGE_SIZE).min(MAX_ROUNDS_PAGE_SIZE);
+
+        self.round_history
+            .iter()
+            .rev()
+            .skip(offset.unwrap_or(0))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    async fn round_count(&self) -> usize {
+        self.round_history.len()
+    }
+
+    /// Whether joining the room requires a passcode.
+    async fn has_passcode(&self) -> bool {
+        self.passcode_hash.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room_with_stories(titles: &[&str]) -> (Room, EntityId, Vec<EntityId>) {
+        let mut room = Room::new(None, ["1", "2", "3"].map(String::from).to_vec());
+        let owner = User::new("owner".to_string());
+        let owner_id = owner.id;
+        room.users.push(owner);
+        room.set_room_owner(Some(owner_id)).unwrap();
+
+        let ids = titles
+            .iter()
+            .map(|title| {
+                let story = Story::new(title.to_string(), None, None).unwrap();
+                let id = story.id;
+                room.add_story(owner_id, story).unwrap();
+                id
+            })
+            .collect();
+        (room, owner_id, ids)
+    }
+
+    fn join(room: &mut Room, name: &str) -> EntityId {
+        let user = User::new(name.to_string());
+        let user_id = user.id;
+        room.users.push(user);
+        user_id
+    }
+
+    fn status(room: &Room, story_id: EntityId) -> StoryStatus {
+        room.stories.iter().find(|story| story.id == story_id).unwrap().status
+    }
+
+    #[test]
+    fn reset_estimates_the_revealed_story_and_selects_the_next() {
+        let (mut room, owner, ids) = room_with_stories(&["first", "second"]);
+        room.select_story(owner, Some(ids[0])).unwrap();
+        room.reveal(Some(owner)).unwrap();
+
+        room.reset_game(Some(owner)).unwrap();
+
+        assert_eq!(status(&room, ids[0]), StoryStatus::Estimated);
+        assert_eq!(status(&room, ids[1]), StoryStatus::Voting);
+        assert_eq!(room.current_story_id, Some(ids[1]));
+    }
+
+    #[test]
+    fn reset_estimates_the_revealed_story_even_after_selecting_another() {
+        let (mut room, owner, ids) = room_with_stories(&["first", "second", "third"]);
+        room.select_story(owner, Some(ids[0])).unwrap();
+        room.reveal(Some(owner)).unwrap();
+        room.select_story(owner, Some(ids[2])).unwrap();
+
+        room.reset_game(Some(owner)).unwrap();
+
+        assert_eq!(status(&room, ids[0]), StoryStatus::Estimated);
+        assert_eq!(status(&room, ids[1]), StoryStatus::Pending);
+        assert_eq!(status(&room, ids[2]), StoryStatus::Voting);
+        assert_eq!(room.current_story_id, Some(ids[2]));
+    }
+
+    #[test]
+    fn setting_a_story_voting_selects_it() {
+        let (mut room, owner, ids) = room_with_stories(&["first", "second"]);
+        room.select_story(owner, Some(ids[0])).unwrap();
+
+        room.update_story(owner, ids[1], None, None, None, Some(StoryStatus::Voting)).unwrap();
+
+        assert_eq!(status(&room, ids[0]), StoryStatus::Pending);
+        assert_eq!(status(&room, ids[1]), StoryStatus::Voting);
+        assert_eq!(room.current_story_id, Some(ids[1]));
+    }
+
+    #[test]
+    fn settling_the_current_story_deselects_it() {
+        let (mut room, owner, ids) = room_with_stories(&["first"]);
+        room.select_story(owner, Some(ids[0])).unwrap();
+
+        room.update_story(owner, ids[0], None, None, None, Some(StoryStatus::Skipped)).unwrap();
+
+        assert_eq!(status(&room, ids[0]), StoryStatus::Skipped);
+        assert_eq!(room.current_story_id, None);
+    }
+
+    #[test]
+    fn spectating_before_the_reveal_withdraws_the_vote() {
+        let (mut room, owner, _) = room_with_stories(&[]);
+        let voter = join(&mut room, "voter");
+        room.cast_vote(voter, "2").unwrap();
+
+        room.set_spectator(voter, voter, true).unwrap();
+
+        assert!(room.game.table.is_empty());
+        assert!(room.is_spectator(voter));
+        room.reveal(Some(owner)).unwrap();
+        assert!(room.round_history[0].votes.is_empty());
+    }
+
+    #[test]
+    fn spectating_after_the_reveal_keeps_the_round_as_played() {
+        let (mut room, owner, _) = room_with_stories(&[]);
+        let voter = join(&mut room, "voter");
+        room.set_vote_lock(owner, VoteLockPolicy::Never).unwrap();
+        room.cast_vote(voter, "2").unwrap();
+        room.cast_vote(owner, "3").unwrap();
+        room.reveal(Some(owner)).unwrap();
+
+        room.set_spectator(owner, voter, true).unwrap();
+
+        assert!(room.is_spectator(voter));
+        assert_eq!(room.game.table.len(), 2);
+        assert_eq!(room.round_history[0].votes.len(), 2);
+        assert_eq!(room.game.statistics.as_ref().unwrap().vote_count, 2);
+    }
+
+    #[test]
+    fn spectating_during_a_locked_countdown_keeps_the_vote() {
+        let (mut room, owner, _) = room_with_stories(&[]);
+        let voter = join(&mut room, "voter");
+        room.set_vote_lock(owner, VoteLockPolicy::AtCountdown).unwrap();
+        room.enable_countdown(owner, true).unwrap();
+        room.cast_vote(voter, "2").unwrap();
+        room.start_countdown(Some(owner)).unwrap();
+
+        room.set_spectator(voter, voter, true).unwrap();
+
+        assert_eq!(room.game.table.len(), 1);
+    }
 }
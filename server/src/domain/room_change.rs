@@ -0,0 +1,210 @@
+use async_graphql::{SimpleObject, Union};
+
+use crate::{
+    simple_broker::{SimpleBroker, Topic},
+    types::{Card, EntityId},
+};
+
+use super::{chat::ChatMessage, deck::Deck, game::Game, room::{RevealStage, Room, VoteLockPolicy}, user::User};
+
+/// One change to a room, as published on the `roomChanges` subscription.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RoomChange {
+    pub room_id: EntityId,
+    /// The room version after this change. Versions go up by exactly one per
+    /// change, so a gap means changes were missed; resync with
+    /// `roomSync(roomId, sinceVersion)`.
+    pub version: u64,
+    pub delta: RoomDelta,
+}
+
+#[derive(Clone, Debug, Union)]
+pub enum RoomDelta {
+    UserJoined(UserJoined),
+    UserLeft(UserLeft),
+    UserUpdated(UserUpdated),
+    VoteCast(VoteCast),
+    CardsRevealed(CardsRevealed),
+    RevealStageChanged(RevealStageChanged),
+    DeckUpdated(DeckUpdated),
+    SettingsChanged(SettingsChanged),
+    ChatMessageSent(ChatMessageSent),
+    /// Anything without a dedicated delta: replace the whole room.
+    RoomReplaced(RoomReplaced),
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserJoined {
+    pub user: User,
+}
+
+/// The user left, was kicked or was banned; their card leaves the table too.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserLeft {
+    pub user_id: EntityId,
+    pub banned: bool,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserUpdated {
+    pub user: User,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct VoteCast {
+    pub user_id: EntityId,
+    /// `false` when the user took their card back.
+    pub voted: bool,
+    /// The card, only once the round has been revealed.
+    pub card: Option<Card>,
+}
+
+/// The round was revealed: `game` holds every card and the statistics.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct CardsRevealed {
+    pub game: Game,
+    pub reveal_stage: Option<RevealStage>,
+}
+
+/// The reveal countdown started, ticked or was cancelled.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RevealStageChanged {
+    pub reveal_stage: Option<RevealStage>,
+    pub countdown_value: Option<i32>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct DeckUpdated {
+    pub deck: Deck,
+}
+
+/// The room's name and settings, all sent together whichever changed.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct SettingsChanged {
+    pub name: Option<String>,
+    pub countdown_enabled: bool,
+    pub countdown_seconds: i32,
+    pub auto_reveal: bool,
+    pub auto_reset_seconds: Option<i32>,
+    pub vote_lock: VoteLockPolicy,
+    pub confirm_new_game: bool,
+    pub has_passcode: bool,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct ChatMessageSent {
+    pub message: ChatMessage,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RoomReplaced {
+    pub room: Box<Room>,
+}
+
+impl RoomDelta {
+    pub fn user_joined(room: &Room, user_id: EntityId) -> Self {
+        match room.users.iter().find(|user| user.id == user_id) {
+            Some(user) => RoomDelta::UserJoined(UserJoined { user: user.clone() }),
+            None => Self::replaced(room),
+        }
+    }
+
+    pub fn user_left(user_id: EntityId, banned: bool) -> Self {
+        RoomDelta::UserLeft(UserLeft { user_id, banned })
+    }
+
+    pub fn user_updated(room: &Room, user_id: EntityId) -> Self {
+        match room.users.iter().find(|user| user.id == user_id) {
+            Some(user) => RoomDelta::UserUpdated(UserUpdated { user: user.clone() }),
+            None => Self::replaced(room),
+        }
+    }
+
+    pub fn vote_cast(room: &Room, user_id: EntityId) -> Self {
+        let card = room
+            .game
+            .table
+            .iter()
+            .find(|uc| uc.user_id == user_id)
+            .map(|uc| uc.card.clone());
+
+        RoomDelta::VoteCast(VoteCast {
+            user_id,
+            voted: card.is_some(),
+            card: card.flatten().filter(|_| room.is_game_over),
+        })
+    }
+
+    pub fn cards_revealed(room: &Room) -> Self {
+        RoomDelta::CardsRevealed(CardsRevealed {
+            game: room.game.clone(),
+            reveal_stage: room.reveal_stage,
+        })
+    }
+
+    pub fn reveal_stage_changed(room: &Room) -> Self {
+        RoomDelta::RevealStageChanged(RevealStageChanged {
+            reveal_stage: room.reveal_stage,
+            countdown_value: room.countdown_value,
+        })
+    }
+
+    pub fn deck_updated(room: &Room) -> Self {
+        RoomDelta::DeckUpdated(DeckUpdated {
+            deck: room.deck.clone(),
+        })
+    }
+
+    pub fn settings_changed(room: &Room) -> Self {
+        RoomDelta::SettingsChanged(SettingsChanged {
+            name: room.name.clone(),
+            countdown_enabled: room.countdown_enabled,
+            countdown_seconds: room.countdown_seconds,
+            auto_reveal: room.auto_reveal,
+            auto_reset_seconds: room.auto_reset_seconds,
+            vote_lock: room.vote_lock,
+            confirm_new_game: room.confirm_new_game,
+            has_passcode: room.passcode_hash.is_some(),
+        })
+    }
+
+    pub fn chat_message_sent(message: ChatMessage) -> Self {
+        RoomDelta::ChatMessageSent(ChatMessageSent { message })
+    }
+
+    pub fn replaced(room: &Room) -> Self {
+        RoomDelta::RoomReplaced(RoomReplaced {
+            room: Box::new(room.get_room()),
+        })
+    }
+}
+
+impl Topic for RoomChange {
+    const SUBSCRIPTION: &'static str = "room_changes";
+
+    fn topic(&self) -> EntityId {
+        self.room_id
+    }
+}
+
+impl RoomChange {
+    /// Publish a change to `room`: the delta to `roomChanges` subscribers and
+    /// a full snapshot to `room` subscribers. Each is only built when someone
+    /// is subscribed to the room.
+    ///
+    /// Call once per [`Room::touch`] or [`Room::bump_version`], so versions
+    /// stay gapless.
+    pub fn publish(room: &Room, delta: impl FnOnce(&Room) -> RoomDelta) {
+        if SimpleBroker::<Room>::has_subscribers(room.id) {
+            SimpleBroker::publish(room.get_room());
+        }
+
+        if SimpleBroker::<RoomChange>::has_subscribers(room.id) {
+            SimpleBroker::publish(RoomChange {
+                room_id: room.id,
+                version: room.version,
+                delta: delta(room),
+            });
+        }
+    }
+}
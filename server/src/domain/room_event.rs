@@ -0,0 +1,200 @@
+use async_graphql::{Enum, SimpleObject, Union};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    simple_broker::{SimpleBroker, Topic},
+    types::{Card, EntityId},
+};
+
+use super::{deck::Deck, game::Game, room::Room};
+
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RoomEventType {
+    UserKicked,
+    UserBanned,
+    RoomExpired,
+    FinalEstimateSet,
+    OwnerChanged,
+    RoomRenamed,
+    DeckChanged,
+    GameReset,
+    CardsRevealed,
+    UserOnline,
+    UserOffline,
+    UserTimedOut,
+}
+
+/// Something that happened in a room, as published on `roomEvents`.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RoomEvent {
+    pub room_id: EntityId,
+    /// Matches the type of `payload`.
+    pub event_type: RoomEventType,
+    /// The user the event is about, for kicks, bans and presence changes.
+    pub target_user_id: Option<EntityId>,
+    pub payload: RoomEventPayload,
+    /// The room after the event.
+    pub room: Room,
+}
+
+#[derive(Clone, Debug, Union)]
+pub enum RoomEventPayload {
+    UserKicked(UserKickedPayload),
+    UserBanned(UserBannedPayload),
+    RoomExpired(RoomExpiredPayload),
+    FinalEstimateSet(FinalEstimateSetPayload),
+    OwnerChanged(OwnerChangedPayload),
+    RoomRenamed(RoomRenamedPayload),
+    DeckChanged(DeckChangedPayload),
+    GameReset(GameResetPayload),
+    CardsRevealed(CardsRevealedPayload),
+    UserOnline(UserOnlinePayload),
+    UserOffline(UserOfflinePayload),
+    UserTimedOut(UserTimedOutPayload),
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserKickedPayload {
+    pub user_id: EntityId,
+    pub kicked_by: EntityId,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserBannedPayload {
+    pub user_id: EntityId,
+    pub banned_by: EntityId,
+}
+
+/// The room was removed for inactivity; it can't be rejoined.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RoomExpiredPayload {
+    pub room_name: Option<String>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct FinalEstimateSetPayload {
+    /// `None` when the estimate was cleared.
+    pub card: Option<Card>,
+    pub story_id: Option<EntityId>,
+    pub set_by: EntityId,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct OwnerChangedPayload {
+    pub previous_owner_id: Option<EntityId>,
+    pub owner_id: Option<EntityId>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct RoomRenamedPayload {
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct DeckChangedPayload {
+    pub deck: Deck,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GameResetPayload {
+    /// The new, empty game.
+    pub game_id: EntityId,
+    /// `None` when the round was reset automatically after the reveal.
+    pub reset_by: Option<EntityId>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct CardsRevealedPayload {
+    /// Every card on the table, with the statistics.
+    pub game: Game,
+    /// `None` for an automatic reveal.
+    pub revealed_by: Option<EntityId>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserOnlinePayload {
+    pub user_id: EntityId,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserOfflinePayload {
+    pub user_id: EntityId,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+/// The user stayed disconnected past the grace period and was removed.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct UserTimedOutPayload {
+    pub user_id: EntityId,
+}
+
+impl RoomEventPayload {
+    pub fn event_type(&self) -> RoomEventType {
+        match self {
+            RoomEventPayload::UserKicked(_) => RoomEventType::UserKicked,
+            RoomEventPayload::UserBanned(_) => RoomEventType::UserBanned,
+            RoomEventPayload::RoomExpired(_) => RoomEventType::RoomExpired,
+            RoomEventPayload::FinalEstimateSet(_) => RoomEventType::FinalEstimateSet,
+            RoomEventPayload::OwnerChanged(_) => RoomEventType::OwnerChanged,
+            RoomEventPayload::RoomRenamed(_) => RoomEventType::RoomRenamed,
+            RoomEventPayload::DeckChanged(_) => RoomEventType::DeckChanged,
+            RoomEventPayload::GameReset(_) => RoomEventType::GameReset,
+            RoomEventPayload::CardsRevealed(_) => RoomEventType::CardsRevealed,
+            RoomEventPayload::UserOnline(_) => RoomEventType::UserOnline,
+            RoomEventPayload::UserOffline(_) => RoomEventType::UserOffline,
+            RoomEventPayload::UserTimedOut(_) => RoomEventType::UserTimedOut,
+        }
+    }
+
+    fn target_user_id(&self) -> Option<EntityId> {
+        match self {
+            RoomEventPayload::UserKicked(payload) => Some(payload.user_id),
+            RoomEventPayload::UserBanned(payload) => Some(payload.user_id),
+            RoomEventPayload::UserOnline(payload) => Some(payload.user_id),
+            RoomEventPayload::UserOffline(payload) => Some(payload.user_id),
+            RoomEventPayload::UserTimedOut(payload) => Some(payload.user_id),
+            _ => None,
+        }
+    }
+
+    pub fn cards_revealed(room: &Room, revealed_by: Option<EntityId>) -> Self {
+        RoomEventPayload::CardsRevealed(CardsRevealedPayload {
+            game: room.game.clone(),
+            revealed_by,
+        })
+    }
+
+    pub fn game_reset(room: &Room, reset_by: Option<EntityId>) -> Self {
+        RoomEventPayload::GameReset(GameResetPayload {
+            game_id: room.game.id,
+            reset_by,
+        })
+    }
+}
+
+impl Topic for RoomEvent {
+    const SUBSCRIPTION: &'static str = "room_events";
+
+    fn topic(&self) -> EntityId {
+        self.room_id
+    }
+}
+
+impl RoomEvent {
+    pub fn new(room: &Room, payload: RoomEventPayload) -> Self {
+        RoomEvent {
+            room_id: room.id,
+            event_type: payload.event_type(),
+            target_user_id: payload.target_user_id(),
+            payload,
+            room: room.get_room(),
+        }
+    }
+
+    /// Publish an event about `room`, built only if someone is listening.
+    pub fn publish(room: &Room, payload: impl FnOnce(&Room) -> RoomEventPayload) {
+        if SimpleBroker::<RoomEvent>::has_subscribers(room.id) {
+            SimpleBroker::publish(RoomEvent::new(room, payload(room)));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Card, EntityId};
+
+use super::{game::UserCard, statistics::VoteStatistics};
+
+/// Rounds kept per room; the oldest are dropped first.
+pub const MAX_ROUND_HISTORY: usize = 200;
+
+pub const DEFAULT_ROUNDS_PAGE_SIZE: usize = 20;
+pub const MAX_ROUNDS_PAGE_SIZE: usize = 100;
+
+/// A revealed round, archived on the room.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct Round {
+    /// 1-based, counting every round revealed in the room.
+    pub number: u32,
+    /// The `Game` this round was played as.
+    pub game_id: EntityId,
+    /// The backlog story voted on, if one was selected.
+    #[serde(default)]
+    pub story_id: Option<EntityId>,
+    pub story_title: Option<String>,
+    pub votes: Vec<UserCard>,
+    #[serde(default)]
+    pub statistics: Option<VoteStatistics>,
+    pub revealed_at: DateTime<Utc>,
+    /// Who revealed the cards (or started the countdown); `None` when it happened automatically.
+    pub revealed_by: Option<EntityId>,
+    /// The estimate the team agreed on, which may differ from every vote.
+    #[serde(default)]
+    pub final_estimate: Option<Card>,
+    #[serde(default)]
+    pub final_estimate_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub final_estimate_by: Option<EntityId>,
+}
@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::types::Card;
+
+use super::{deck::Deck, game::UserCard};
+
+/// Summary of a revealed round's votes.
+///
+/// Only cards with a value in the deck count towards the averages; the rest
+/// ("?", "☕", ...) are reported in `non_numeric_count`. Numeric fields are `None` when nobody picked
+/// a numeric card.
+#[derive(Clone, Debug, Default, SimpleObject, Serialize, Deserialize)]
+pub struct VoteStatistics {
+    pub vote_count: usize,
+    pub numeric_count: usize,
+    pub non_numeric_count: usize,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    /// Most frequent values, ascending; several on a tie.
+    pub mode: Vec<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Population standard deviation.
+    pub std_dev: Option<f64>,
+    /// The numeric deck card closest to the mean.
+    pub nearest_card: Option<Card>,
+    /// Everyone picked the same card.
+    pub consensus: bool,
+}
+
+impl VoteStatistics {
+    pub fn compute(votes: &[UserCard], deck: &Deck) -> Self {
+        let cards: Vec<&str> = votes.iter().filter_map(|vote| vote.card.as_deref()).collect();
+
+        let mut values: Vec<f64> = cards
+            .iter()
+            .filter_map(|card| deck.value_of(card))
+            .collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let mut stats = VoteStatistics {
+            vote_count: cards.len(),
+            numeric_count: values.len(),
+            non_numeric_count: cards.len() - values.len(),
+            consensus: !cards.is_empty() && cards.iter().all(|card| *card == cards[0]),
+            ..Default::default()
+        };
+
+        if values.is_empty() {
+            return stats;
+        }
+
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+
+        let middle = values.len() / 2;
+        let median = if values.len().is_multiple_of(2) {
+            (values[middle - 1] + values[middle]) / 2.0
+        } else {
+            values[middle]
+        };
+
+        stats.mean = Some(mean);
+        stats.median = Some(median);
+        stats.mode = modes(&values);
+        stats.min = values.first().copied();
+        stats.max = values.last().copied();
+        stats.std_dev = Some(variance.sqrt());
+        stats.nearest_card = nearest_card(deck, mean);
+
+        stats
+    }
+}
+
+/// Most frequent values of an already sorted slice.
+fn modes(sorted: &[f64]) -> Vec<f64> {
+    let mut runs: Vec<(f64, usize)> = Vec::new();
+    for &value in sorted {
+        match runs.last_mut() {
+            Some((last, count)) if *last == value => *count += 1,
+            _ => runs.push((value, 1)),
+        }
+    }
+
+    let best = runs.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    runs.into_iter()
+        .filter(|(_, count)| *count == best)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+fn nearest_card(deck: &Deck, target: f64) -> Option<Card> {
+    deck.cards
+        .iter()
+        .filter_map(|card| card.value.map(|value| (&card.label, value)))
+        .min_by(|(_, a), (_, b)| {
+            (a - target)
+                .abs()
+                .partial_cmp(&(b - target).abs())
+                .unwrap_or(Ordering::Equal)
+        })
+        .map(|(card, _)| card.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn deck() -> Deck {
+        Deck::new_with_cards(["1", "2", "3", "5", "8", "13", "?", "☕"].map(String::from).to_vec())
+    }
+
+    fn votes(cards: &[&str]) -> Vec<UserCard> {
+        cards
+            .iter()
+            .map(|card| UserCard::new(Uuid::new_v4(), card.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn no_votes_have_no_numbers() {
+        let stats = VoteStatistics::compute(&[], &deck());
+
+        assert_eq!(stats.vote_count, 0);
+        assert_eq!(stats.mean, None);
+        assert_eq!(stats.median, None);
+        assert!(stats.mode.is_empty());
+        assert_eq!(stats.nearest_card, None);
+        assert!(!stats.consensus);
+    }
+
+    #[test]
+    fn withdrawn_votes_are_not_counted() {
+        let mut table = votes(&["3"]);
+        table.push(UserCard {
+            user_id: Uuid::new_v4(),
+            card: None,
+        });
+
+        let stats = VoteStatistics::compute(&table, &deck());
+
+        assert_eq!(stats.vote_count, 1);
+        assert!(stats.consensus);
+    }
+
+    #[test]
+    fn non_numeric_cards_are_counted_apart() {
+        let stats = VoteStatistics::compute(&votes(&["2", "?", "☕", "8"]), &deck());
+
+        assert_eq!(stats.vote_count, 4);
+        assert_eq!(stats.numeric_count, 2);
+        assert_eq!(stats.non_numeric_count, 2);
+        assert_eq!(stats.mean, Some(5.0));
+        assert_eq!(stats.median, Some(5.0));
+        assert_eq!(stats.nearest_card.as_deref(), Some("5"));
+        assert!(!stats.consensus);
+    }
+
+    #[test]
+    fn only_non_numeric_cards_leave_the_numbers_empty() {
+        let stats = VoteStatistics::compute(&votes(&["?", "?"]), &deck());
+
+        assert_eq!(stats.numeric_count, 0);
+        assert_eq!(stats.mean, None);
+        assert_eq!(stats.std_dev, None);
+        // Agreeing that nobody knows is still agreeing.
+        assert!(stats.consensus);
+    }
+
+    #[test]
+    fn median_of_an_even_count_is_the_midpoint() {
+        let stats = VoteStatistics::compute(&votes(&["8", "1", "3", "5"]), &deck());
+
+        assert_eq!(stats.median, Some(4.0));
+        assert_eq!(stats.mean, Some(4.25));
+    }
+
+    #[test]
+    fn tied_modes_are_all_reported_ascending() {
+        let stats = VoteStatistics::compute(&votes(&["5", "3", "5", "3", "8"]), &deck());
+
+        assert_eq!(stats.mode, vec![3.0, 5.0]);
+        assert_eq!(stats.median, Some(5.0));
+        assert!(!stats.consensus);
+    }
+
+    #[test]
+    fn nearest_card_tie_goes_to_the_lower_card() {
+        // The mean of 2.5 is as close to 2 as to 3.
+        let stats = VoteStatistics::compute(&votes(&["2", "3"]), &deck());
+
+        assert_eq!(stats.mean, Some(2.5));
+        assert_eq!(stats.nearest_card.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn an_outlier_pulls_the_mean_but_not_the_median() {
+        let stats = VoteStatistics::compute(&votes(&["2", "2", "2", "13"]), &deck());
+
+        assert_eq!(stats.min, Some(2.0));
+        assert_eq!(stats.max, Some(13.0));
+        assert_eq!(stats.median, Some(2.0));
+        assert_eq!(stats.mean, Some(4.75));
+        assert!((stats.std_dev.unwrap() - 4.7631).abs() < 1e-4);
+        assert_eq!(stats.nearest_card.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn consensus_means_the_same_card_with_no_spread() {
+        let stats = VoteStatistics::compute(&votes(&["5", "5", "5"]), &deck());
+
+        assert!(stats.consensus);
+        assert_eq!(stats.mode, vec![5.0]);
+        assert_eq!(stats.std_dev, Some(0.0));
+    }
+}
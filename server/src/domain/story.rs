@@ -0,0 +1,105 @@
+use async_graphql::{Enum, MaybeUndefined, SimpleObject};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::types::{Card, EntityId};
+
+use super::error::RoomError;
+
+pub const MAX_STORIES: usize = 500;
+pub const MAX_STORY_TITLE_LENGTH: usize = 200;
+pub const MAX_STORY_DESCRIPTION_LENGTH: usize = 5000;
+pub const MAX_STORY_KEY_LENGTH: usize = 64;
+
+#[derive(Enum, Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoryStatus {
+    /// Waiting its turn in the backlog.
+    #[default]
+    Pending,
+    /// Currently being voted on.
+    Voting,
+    /// Voted on and left behind.
+    Estimated,
+    /// Passed over without an estimate.
+    Skipped,
+}
+
+/// An item of the room backlog.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct Story {
+    pub id: EntityId,
+    pub title: String,
+    pub description: Option<String>,
+    /// Key in an external tracker, e.g. `PROJ-123`.
+    pub external_key: Option<String>,
+    pub final_estimate: Option<Card>,
+    pub status: StoryStatus,
+}
+
+impl Story {
+    pub fn new(
+        title: String,
+        description: Option<String>,
+        external_key: Option<String>,
+    ) -> Result<Self, RoomError> {
+        Ok(Story {
+            id: Uuid::new_v4(),
+            title: validate_title(title)?,
+            description: validate_optional(description, "Description", MAX_STORY_DESCRIPTION_LENGTH)?,
+            external_key: validate_optional(external_key, "External key", MAX_STORY_KEY_LENGTH)?,
+            final_estimate: None,
+            status: StoryStatus::Pending,
+        })
+    }
+}
+
+pub fn validate_title(title: String) -> Result<String, RoomError> {
+    let title = title.trim().to_string();
+
+    if title.is_empty() {
+        return Err(RoomError::InvalidSetting("Story title can't be empty".to_string()));
+    }
+
+    if title.chars().count() > MAX_STORY_TITLE_LENGTH {
+        return Err(RoomError::InvalidSetting(format!(
+            "Story title can't be longer than {} characters",
+            MAX_STORY_TITLE_LENGTH
+        )));
+    }
+
+    Ok(title)
+}
+
+/// Trim an optional text field; blank becomes `None`.
+pub fn validate_optional(
+    value: Option<String>,
+    field: &str,
+    max_length: usize,
+) -> Result<Option<String>, RoomError> {
+    let value = value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    if let Some(v) = &value
+        && v.chars().count() > max_length
+    {
+        return Err(RoomError::InvalidSetting(format!(
+            "{} can't be longer than {} characters",
+            field, max_length
+        )));
+    }
+
+    Ok(value)
+}
+
+/// Validate a patch of an optional field: `None` leaves it unchanged, `Some(None)` clears it.
+pub fn validate_update(
+    value: MaybeUndefined<String>,
+    field: &str,
+    max_length: usize,
+) -> Result<Option<Option<String>>, RoomError> {
+    match value {
+        MaybeUndefined::Undefined => Ok(None),
+        MaybeUndefined::Null => Ok(Some(None)),
+        MaybeUndefined::Value(v) => validate_optional(Some(v), field, max_length).map(Some),
+    }
+}
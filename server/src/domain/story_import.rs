@@ -0,0 +1,312 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::Deserialize;
+
+use super::{error::RoomError, story::Story};
+
+/// Largest accepted import payload, in bytes.
+pub const MAX_IMPORT_BYTES: usize = 1024 * 1024;
+
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StoryImportFormat {
+    /// Comma-separated values with a header row; see [`CsvColumnMapping`].
+    Csv,
+    /// An array of `{ "title", "description", "externalKey" }` objects.
+    Json,
+}
+
+/// Which CSV header holds each story field. Headers match case-insensitively.
+#[derive(Clone, Debug, InputObject)]
+pub struct CsvColumnMapping {
+    #[graphql(default = "title")]
+    pub title: String,
+    #[graphql(default = "description")]
+    pub description: String,
+    #[graphql(default = "key")]
+    pub external_key: String,
+}
+
+impl Default for CsvColumnMapping {
+    fn default() -> Self {
+        CsvColumnMapping {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            external_key: "key".to_string(),
+        }
+    }
+}
+
+/// A row that couldn't be imported.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct StoryImportError {
+    /// Line number for CSV, 1-based array index for JSON.
+    pub row: usize,
+    pub message: String,
+}
+
+/// Stories parsed from an import, plus the rows that were rejected.
+#[derive(Debug, Default)]
+pub struct ParsedStories {
+    pub stories: Vec<(usize, Story)>,
+    pub errors: Vec<StoryImportError>,
+}
+
+impl ParsedStories {
+    fn push(&mut self, row: usize, story: Result<Story, RoomError>) {
+        match story {
+            Ok(story) => self.stories.push((row, story)),
+            Err(err) => self.errors.push(StoryImportError {
+                row,
+                message: err.message(),
+            }),
+        }
+    }
+}
+
+/// Parse an import. Only a payload that can't be read at all is an error;
+/// bad rows are collected in [`ParsedStories::errors`].
+pub fn parse_stories(
+    format: StoryImportFormat,
+    data: &str,
+    columns: &CsvColumnMapping,
+) -> Result<ParsedStories, RoomError> {
+    if data.len() > MAX_IMPORT_BYTES {
+        return Err(RoomError::InvalidImport(format!(
+            "Imports are limited to {} bytes",
+            MAX_IMPORT_BYTES
+        )));
+    }
+
+    match format {
+        StoryImportFormat::Csv => parse_csv(data, columns),
+        StoryImportFormat::Json => parse_json(data),
+    }
+}
+
+fn parse_csv(data: &str, columns: &CsvColumnMapping) -> Result<ParsedStories, RoomError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|err| RoomError::InvalidImport(format!("Could not read the CSV header: {}", err)))?
+        .clone();
+
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name.trim()))
+    };
+
+    let title_column = column(&columns.title).ok_or_else(|| {
+        RoomError::InvalidImport(format!("The CSV has no \"{}\" column", columns.title))
+    })?;
+    let description_column = column(&columns.description);
+    let key_column = column(&columns.external_key);
+
+    let mut parsed = ParsedStories::default();
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                let row = err.position().map_or(0, |p| p.line() as usize);
+                parsed.errors.push(StoryImportError {
+                    row,
+                    message: format!("Could not read the row: {}", err),
+                });
+                continue;
+            }
+        };
+
+        let row = record.position().map_or(0, |p| p.line() as usize);
+        let field = |index: Option<usize>| index.and_then(|i| record.get(i)).map(str::to_string);
+
+        parsed.push(
+            row,
+            Story::new(
+                field(Some(title_column)).unwrap_or_default(),
+                field(description_column),
+                field(key_column),
+            ),
+        );
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonStory {
+    title: String,
+    description: Option<String>,
+    #[serde(alias = "key", alias = "external_key")]
+    external_key: Option<String>,
+}
+
+fn parse_json(data: &str) -> Result<ParsedStories, RoomError> {
+    let rows: Vec<serde_json::Value> = serde_json::from_str(data).map_err(|err| {
+        RoomError::InvalidImport(format!("Expected a JSON array of stories: {}", err))
+    })?;
+
+    let mut parsed = ParsedStories::default();
+
+    for (index, value) in rows.into_iter().enumerate() {
+        let row = index + 1;
+        let story = serde_json::from_value::<JsonStory>(value)
+            .map_err(|err| RoomError::InvalidSetting(format!("Invalid story: {}", err)))
+            .and_then(|s| Story::new(s.title, s.description, s.external_key));
+
+        parsed.push(row, story);
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        room::Room,
+        story::{MAX_STORIES, MAX_STORY_DESCRIPTION_LENGTH},
+        user::User,
+    };
+
+    use super::*;
+
+    fn csv(data: &str) -> ParsedStories {
+        parse_stories(StoryImportFormat::Csv, data, &CsvColumnMapping::default()).unwrap()
+    }
+
+    fn json(data: &str) -> ParsedStories {
+        parse_stories(StoryImportFormat::Json, data, &CsvColumnMapping::default()).unwrap()
+    }
+
+    fn titles(parsed: &ParsedStories) -> Vec<&str> {
+        parsed.stories.iter().map(|(_, story)| story.title.as_str()).collect()
+    }
+
+    fn error_rows(parsed: &ParsedStories) -> Vec<usize> {
+        parsed.errors.iter().map(|error| error.row).collect()
+    }
+
+    #[test]
+    fn csv_reads_quoted_fields() {
+        let parsed = csv(
+            "Title,Description,Key\n\
+             \"Login, then logout\",\"Say \"\"hi\"\"\nover two lines\",PROJ-1\n",
+        );
+
+        let (row, story) = &parsed.stories[0];
+        assert_eq!(*row, 2);
+        assert_eq!(story.title, "Login, then logout");
+        assert_eq!(story.description.as_deref(), Some("Say \"hi\"\nover two lines"));
+        assert_eq!(story.external_key.as_deref(), Some("PROJ-1"));
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn csv_matches_mapped_columns_case_insensitively() {
+        let columns = CsvColumnMapping {
+            title: "Summary".to_string(),
+            description: "details".to_string(),
+            external_key: "Issue key".to_string(),
+        };
+        let parsed = parse_stories(
+            StoryImportFormat::Csv,
+            "issue key,SUMMARY\nPROJ-7,Checkout\n",
+            &columns,
+        )
+        .unwrap();
+
+        assert_eq!(titles(&parsed), ["Checkout"]);
+        assert_eq!(parsed.stories[0].1.external_key.as_deref(), Some("PROJ-7"));
+        assert_eq!(parsed.stories[0].1.description, None);
+    }
+
+    #[test]
+    fn csv_collects_malformed_rows_by_line() {
+        let parsed = csv("title,description\nFirst,ok\n,no title\nSecond\n   ,blank\n");
+
+        assert_eq!(titles(&parsed), ["First", "Second"]);
+        assert_eq!(error_rows(&parsed), [3, 5]);
+    }
+
+    #[test]
+    fn csv_without_a_title_column_is_rejected() {
+        let result = parse_stories(
+            StoryImportFormat::Csv,
+            "name,description\nFirst,ok\n",
+            &CsvColumnMapping::default(),
+        );
+
+        assert!(matches!(result, Err(RoomError::InvalidImport(_))));
+    }
+
+    #[test]
+    fn json_collects_invalid_entries_by_index() {
+        let parsed = json(
+            r#"[
+                { "title": "First", "key": "PROJ-1" },
+                { "description": "no title" },
+                "not a story",
+                { "title": "Second", "externalKey": "PROJ-2" }
+            ]"#,
+        );
+
+        assert_eq!(titles(&parsed), ["First", "Second"]);
+        assert_eq!(parsed.stories[1].1.external_key.as_deref(), Some("PROJ-2"));
+        assert_eq!(error_rows(&parsed), [2, 3]);
+    }
+
+    #[test]
+    fn json_that_is_not_an_array_is_rejected() {
+        let result = parse_stories(
+            StoryImportFormat::Json,
+            r#"{ "title": "First" }"#,
+            &CsvColumnMapping::default(),
+        );
+
+        assert!(matches!(result, Err(RoomError::InvalidImport(_))));
+    }
+
+    #[test]
+    fn descriptions_over_the_limit_reject_their_row() {
+        let at_limit = "a".repeat(MAX_STORY_DESCRIPTION_LENGTH);
+        let over_limit = "a".repeat(MAX_STORY_DESCRIPTION_LENGTH + 1);
+        let parsed = csv(&format!("title,description\nFits,{at_limit}\nToo long,{over_limit}\n"));
+
+        assert_eq!(titles(&parsed), ["Fits"]);
+        assert_eq!(error_rows(&parsed), [3]);
+    }
+
+    #[test]
+    fn oversized_payloads_are_rejected() {
+        let data = format!("title\n{}", "a\n".repeat(MAX_IMPORT_BYTES / 2 + 1));
+
+        let result = parse_stories(StoryImportFormat::Csv, &data, &CsvColumnMapping::default());
+
+        assert!(matches!(result, Err(RoomError::InvalidImport(_))));
+    }
+
+    #[test]
+    fn stories_past_the_room_limit_are_reported() {
+        let rows: String = (1..=MAX_STORIES + 2).map(|i| format!("Story {i}\n")).collect();
+        let parsed = csv(&format!("title\n{rows}"));
+        assert_eq!(parsed.stories.len(), MAX_STORIES + 2);
+
+        let mut room = Room::new(None, vec![]);
+        let owner = User::new("owner".to_string());
+        let owner_id = owner.id;
+        room.users.push(owner);
+        room.set_room_owner(Some(owner_id)).unwrap();
+
+        let (imported, rejected) = room.import_stories(owner_id, parsed.stories).unwrap();
+
+        assert_eq!(imported, MAX_STORIES);
+        assert_eq!(room.stories.len(), MAX_STORIES);
+        // The header is line 1, so story n is on line n + 1.
+        let rows: Vec<_> = rejected.iter().map(|error| error.row).collect();
+        assert_eq!(rows, [MAX_STORIES + 2, MAX_STORIES + 3]);
+    }
+}
@@ -1,23 +1,35 @@
 use async_graphql::{InputObject, SimpleObject};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::types::EntityId;
 
 #[derive(Clone, Debug, InputObject)]
 pub struct UserInput {
-    pub id: EntityId,
     pub username: String,
     pub room_name: Option<String>,
     pub last_card_picked: Option<String>,
+    /// Join as a spectator: watch and chat, but don't vote.
+    pub is_spectator: Option<bool>,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct User {
     pub id: EntityId,
     pub username: String,
     pub last_card_picked: Option<String>,
     pub last_card_value: Option<f32>,
-    pub last_seen_chat_message_id: Option<Uuid>
+    pub last_seen_chat_message_id: Option<Uuid>,
+    /// Spectators watch and chat but don't vote or count towards the vote.
+    #[serde(default)]
+    pub is_spectator: bool,
+    /// Whether the user has a live subscription to the room.
+    #[serde(default)]
+    pub online: bool,
+    /// When the user last connected to or disconnected from the room.
+    #[serde(default)]
+    pub last_seen: Option<DateTime<Utc>>,
 }
 
 impl User {
@@ -27,47 +39,29 @@ impl User {
             username,
             last_card_picked: None,
             last_card_value: None,
-            last_seen_chat_message_id: None
+            last_seen_chat_message_id: None,
+            is_spectator: false,
+            online: false,
+            last_seen: None,
         }
     }
-}
 
-impl From<UserInput> for User {
-    fn from(input: UserInput) -> Self {
-        let last_card_value = input
-            .last_card_picked
-            .as_deref()
-            .and_then(|s| crate::domain::user::parse_card_to_number(s));
+    /// Build the room's record of the authenticated user `id` joining with `input`.
+    ///
+    /// `last_card_value` is left for the room to fill in from its deck.
+    pub fn from_input(id: EntityId, input: UserInput) -> Self {
+        let is_spectator = input.is_spectator.unwrap_or(false);
 
         User {
-            id: input.id,
+            id,
             username: input.username,
-            last_card_picked: input.last_card_picked,
-            last_card_value,
-            last_seen_chat_message_id: None
+            // Spectators have nothing on the table to restore.
+            last_card_picked: input.last_card_picked.filter(|_| !is_spectator),
+            last_card_value: None,
+            last_seen_chat_message_id: None,
+            is_spectator,
+            online: false,
+            last_seen: None,
         }
     }
 }
-
-/// Small helper to coerce common card representations into a numeric f32, or None.
-pub fn parse_card_to_number(s: &str) -> Option<f32> {
-    let s = s.trim();
-
-    if s.is_empty() {
-        return None;
-    }
-
-    if s == "?" || s == "☕" {
-        return None;
-    }
-
-    if s == "½" || s == "1/2" {
-        return Some(0.5);
-    }
-
-    if let Ok(v) = s.parse::<f32>() {
-        return Some(v);
-    }
-
-    None
-}
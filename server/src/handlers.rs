@@ -1,11 +1,41 @@
-use actix_web::{web, HttpRequest, HttpResponse, Result};
+use actix_web::{
+    http::header::{ContentDisposition, AUTHORIZATION},
+    web, HttpRequest, HttpResponse, Result,
+};
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
-    Schema,
+    Data, Schema,
 };
 use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
 
-use crate::schema::PokerPlanningSchema;
+use crate::{
+    auth::{
+        unauthenticated, AdminAccess, AdminCredential, AuthenticatedUser, SessionSigner,
+        ADMIN_TOKEN_HEADER, CONNECTION_TOKEN_KEY,
+    },
+    report::{ReportFormat, SessionReport},
+    schema::PokerPlanningSchema,
+    types::{EntityId, Repository},
+};
+
+/// Resolve the caller from the `Authorization: Bearer <token>` header, if any.
+fn authenticated_user(request: &HttpRequest, signer: &SessionSigner) -> Option<AuthenticatedUser> {
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| signer.verify_bearer(value))
+        .map(AuthenticatedUser)
+}
+
+/// Whether the request carries the configured admin token.
+fn has_admin_access(request: &HttpRequest, admin: &AdminCredential) -> bool {
+    request
+        .headers()
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| admin.verify(value))
+}
 
 pub async fn health_check() -> HttpResponse {
     HttpResponse::Ok().finish()
@@ -13,9 +43,22 @@ pub async fn health_check() -> HttpResponse {
 
 pub async fn index(
     schema: web::Data<PokerPlanningSchema>,
+    signer: web::Data<SessionSigner>,
+    admin: web::Data<AdminCredential>,
+    http_request: HttpRequest,
     request: GraphQLRequest,
 ) -> GraphQLResponse {
-    schema.execute(request.into_inner()).await.into()
+    let mut request = request.into_inner();
+
+    if let Some(user) = authenticated_user(&http_request, &signer) {
+        request = request.data(user);
+    }
+
+    if has_admin_access(&http_request, &admin) {
+        request = request.data(AdminAccess);
+    }
+
+    schema.execute(request).await.into()
 }
 
 pub async fn index_playground() -> Result<HttpResponse> {
@@ -27,8 +70,62 @@ pub async fn index_playground() -> Result<HttpResponse> {
 
 pub async fn index_ws(
     schema: web::Data<PokerPlanningSchema>,
+    signer: web::Data<SessionSigner>,
     request: HttpRequest,
     payload: web::Payload,
 ) -> Result<HttpResponse> {
-    GraphQLSubscription::new(Schema::clone(&*schema)).start(&request, payload)
+    let signer = SessionSigner::clone(&signer);
+
+    GraphQLSubscription::new(Schema::clone(&*schema))
+        .on_connection_init(move |init_payload| async move {
+            let mut data = Data::default();
+
+            // The connection itself may be anonymous (room subscriptions then
+            // fail), but a token that is sent must be genuine.
+            if let Some(token) = init_payload.get(CONNECTION_TOKEN_KEY).and_then(|v| v.as_str()) {
+                let user_id = signer.verify(token).ok_or_else(unauthenticated)?;
+                data.insert(AuthenticatedUser(user_id));
+            }
+
+            Ok(data)
+        })
+        .start(&request, payload)
+}
+
+/// `GET /rooms/{room_id}/report.{csv,json,md}`: the session report, for room members only.
+pub async fn room_report(
+    repository: web::Data<Repository>,
+    signer: web::Data<SessionSigner>,
+    admin: web::Data<AdminCredential>,
+    request: HttpRequest,
+    path: web::Path<(EntityId, String)>,
+) -> HttpResponse {
+    let (room_id, extension) = path.into_inner();
+
+    let Some(format) = ReportFormat::from_extension(&extension) else {
+        return HttpResponse::NotFound().finish();
+    };
+
+    let is_admin = has_admin_access(&request, &admin);
+    let user = authenticated_user(&request, &signer);
+    if user.is_none() && !is_admin {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    // Non-members get the same answer as for a missing room.
+    let room = match repository.get(room_id).await {
+        Some(room) if is_admin || user.is_some_and(|u| room.is_user_exist(u.0)) => room,
+        _ => return HttpResponse::NotFound().finish(),
+    };
+
+    let body = SessionReport::from_room(&room.get_room()).render(format);
+
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(ContentDisposition::attachment(format!(
+            "room-{}-report.{}",
+            room_id,
+            format.extension()
+        )))
+        .body(body)
 }
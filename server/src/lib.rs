@@ -0,0 +1,12 @@
+pub mod auth;
+pub mod configuration;
+pub mod domain;
+pub mod handlers;
+pub mod persistence;
+pub mod presence;
+pub mod report;
+pub mod repository;
+pub mod scheduler;
+pub mod schema;
+pub mod simple_broker;
+pub mod types;
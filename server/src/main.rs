@@ -1,8 +1,18 @@
-use crate::{
-    configuration::get_configuration,
-    handlers::{health_check, index, index_playground, index_ws},
-    schema::{MutationRoot, QueryRoot, SubscriptionRoot, RoomEvent},
-    types::Storage,
+use planning_poker_server::{
+    auth::{AdminCredential, SessionSigner},
+    configuration::{get_configuration, PersistenceBackend},
+    handlers::{health_check, index, index_playground, index_ws, room_report},
+    persistence::{AppendOnlyFileStore, MemoryStore},
+    presence::PresenceTracker,
+    repository::InMemoryRoomRepository,
+    scheduler::RoomScheduler,
+    simple_broker::{self, BrokerMetrics},
+    domain::{
+        room_change::{RoomChange, RoomDelta},
+        room_event::{RoomEvent, RoomEventPayload, RoomExpiredPayload},
+    },
+    schema::{MutationRoot, QueryRoot, SubscriptionRoot},
+    types::{Persistence, Repository},
 };
 use actix_cors::Cors;
 use actix_web::{
@@ -14,23 +24,16 @@ use async_graphql::Schema;
 use env_logger::Env;
 use log::{info, warn, debug};
 
-mod configuration;
-mod domain;
-mod handlers;
-mod schema;
-mod simple_broker;
-mod types;
-
-use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
+use std::{sync::Arc, time::Duration as StdDuration};
 use std::env;
-use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use prometheus::{Encoder, TextEncoder, Registry, IntCounter, IntGauge};
+use prometheus::{Encoder, TextEncoder, Registry, IntCounter, IntCounterVec, IntGauge, Opts};
 use sysinfo::{Pid, System};
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_room_cleanup_task(
-    storage: Storage,
+    repository: Repository,
     check_interval: StdDuration,
     room_ttl: StdDuration,
     rooms_evicted: IntCounter,
@@ -66,9 +69,8 @@ fn spawn_room_cleanup_task(
             let total_seen;
 
             {
-                debug!("cleanup: acquiring storage lock for snapshot");
-                let mut guard = storage.lock().await;
-                total_seen = guard.len();
+                debug!("cleanup: scanning rooms");
+                total_seen = repository.count().await;
                 rooms_current.set(total_seen as i64);
                 rooms_scanned.inc_by(total_seen as u64);
 
@@ -76,36 +78,66 @@ fn spawn_room_cleanup_task(
                 const PER_USER_BYTES: usize = 180;
                 const PER_DECK_CARD_BYTES: usize = 16;
 
-                for (id, room) in guard.iter_mut() {
-                    let removed = room.prune_chat_history(chrono::Duration::hours(48));
-                    if removed > 0 {
+                let chat_max_age = chrono::Duration::hours(48);
+                let now = chrono::Utc::now();
+                let mut prune_ids: Vec<Uuid> = Vec::new();
+
+                repository
+                    .for_each(&mut |room| {
+                        // Chat history is chronological, so the first message is the oldest.
+                        if room
+                            .chat_history
+                            .first()
+                            .is_some_and(|msg| now - msg.timestamp >= chat_max_age)
+                        {
+                            prune_ids.push(room.id);
+                        }
+
+                        let est = BASE_PER_ROOM
+                            + room.users.len().saturating_mul(PER_USER_BYTES)
+                            + room.deck.cards.len().saturating_mul(PER_DECK_CARD_BYTES);
+
+                        total_estimated_bytes = total_estimated_bytes.saturating_add(est);
+
+                        scan_infos.push(RoomScanInfo {
+                            id: room.id,
+                            name: room.name.clone(),
+                            last_active: room.last_active,
+                            estimated_bytes: est
+                        });
+
+                        if room.is_safe_to_remove() && room.is_inactive(room_ttl) {
+                            stale_ids.push(room.id);
+                        }
+                    })
+                    .await;
+
+                for id in prune_ids {
+                    if let Ok(removed) = repository
+                        .update_with(id, |room| {
+                            let removed = room.prune_chat_history(chat_max_age);
+                            if removed > 0 {
+                                // Nobody acted, but clients must drop the messages too.
+                                room.bump_version();
+                                RoomChange::publish(room, RoomDelta::replaced);
+                            }
+                            Ok(removed)
+                        })
+                        .await
+                        && removed > 0
+                    {
                         info!("Pruned {} old chat messages from room {}", removed, id);
                     }
-
-                    let est = BASE_PER_ROOM
-                        + room.users.len().saturating_mul(PER_USER_BYTES)
-                        + room.deck.cards.len().saturating_mul(PER_DECK_CARD_BYTES);
-
-                    total_estimated_bytes = total_estimated_bytes.saturating_add(est);
-
-                    scan_infos.push(RoomScanInfo {
-                        id: *id,
-                        name: room.name.clone(),
-                        last_active: room.last_active,
-                        estimated_bytes: est
-                    });
-
-                    if room.is_safe_to_remove() && room.is_inactive(room_ttl) {
-                        stale_ids.push(*id);
-                    }
                 }
 
                 rooms_total_bytes_estimate.set(total_estimated_bytes as i64);
-                rooms_avg_bytes_estimate.set(if total_seen > 0 {
-                    (total_estimated_bytes / total_seen) as i64
-                } else {
-                    0
-                });
+                rooms_avg_bytes_estimate.set(
+                    total_estimated_bytes.checked_div(total_seen).unwrap_or(0) as i64
+                );
+
+                if let Err(err) = repository.compact().await {
+                    warn!("cleanup: failed to compact room persistence: {}", err);
+                }
             }
 
             if !scan_infos.is_empty() {
@@ -154,10 +186,7 @@ fn spawn_room_cleanup_task(
             let mut users_removed = 0usize;
 
             for id in stale_ids {
-                if let Some(expired_room) = {
-                    let mut guard = storage.lock().await;
-                    guard.remove(&id)
-                } {
+                if let Some(expired_room) = repository.remove(id).await {
                     let user_count = expired_room.users.len();
                     users_removed += user_count;
                     rooms_removed += 1;
@@ -169,14 +198,11 @@ fn spawn_room_cleanup_task(
                         user_count
                     );
 
-                    let event = RoomEvent {
-                        room_id: id,
-                        event_type: "ROOM_EXPIRED".to_string(),
-                        target_user_id: None,
-                        room: expired_room.get_room(),
-                    };
-
-                    crate::simple_broker::SimpleBroker::publish(event);
+                    RoomEvent::publish(&expired_room, |room| {
+                        RoomEventPayload::RoomExpired(RoomExpiredPayload {
+                            room_name: room.name.clone(),
+                        })
+                    });
                     rooms_evicted.inc();
                 } else {
                     warn!("Room cleanup: attempted to remove stale room {}, but it was not found (race?)", id);
@@ -191,8 +217,9 @@ fn spawn_room_cleanup_task(
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_heartbeat_task(
-    storage: Storage,
+    repository: Repository,
     interval: StdDuration,
     total_users_gauge: IntGauge,
     avg_users_per_room_gauge: IntGauge,
@@ -217,13 +244,14 @@ fn spawn_heartbeat_task(
 
             // Snapshot under lock
             let (room_count, total_users, total_deck_cards, total_chat_messages, total_chat_bytes) = {
-                let guard = storage.lock().await;
+                let mut room_count = 0usize;
                 let mut total_users = 0usize;
                 let mut total_deck_cards = 0usize;
                 let mut total_chat_messages = 0usize;
                 let mut total_chat_bytes = 0usize;
 
-                for (_id, room) in guard.iter() {
+                repository.for_each(&mut |room| {
+                    room_count += 1;
                     total_users += room.users.len();
                     total_deck_cards += room.deck.cards.len();
                     total_chat_messages += room.chat_history.len();
@@ -238,9 +266,9 @@ fn spawn_heartbeat_task(
                         let content_len = msg.content.len();
                         total_chat_bytes = total_chat_bytes.saturating_add(formatted_len.max(content_len));
                     }
-                }
+                }).await;
 
-                (guard.len(), total_users, total_deck_cards, total_chat_messages, total_chat_bytes)
+                (room_count, total_users, total_deck_cards, total_chat_messages, total_chat_bytes)
             };
 
             // ---- Derived stats ----
@@ -322,7 +350,7 @@ async fn main() -> std::io::Result<()> {
     let port = env::var("PORT").unwrap_or_else(|_| {
         configured_addr
             .split(':')
-            .last()
+            .next_back()
             .unwrap_or("8000")
             .to_string()
     });
@@ -332,7 +360,42 @@ async fn main() -> std::io::Result<()> {
     println!("Playground (local): http://127.0.0.1:{}", port);
     println!("Server will bind to: {}", server_bind_addr);
 
-    let storage: Storage = Arc::new(Mutex::new(HashMap::new()));
+    let persistence: Persistence = match settings.persistence.backend {
+        PersistenceBackend::Memory => {
+            info!("Room persistence disabled; rooms are kept in memory only");
+            Arc::new(MemoryStore)
+        }
+        PersistenceBackend::File => {
+            info!("Room persistence: append-only log at {}", settings.persistence.path);
+            Arc::new(
+                AppendOnlyFileStore::open(&settings.persistence.path)
+                    .expect("Failed to open room persistence log."),
+            )
+        }
+    };
+
+    let mut restored_rooms = persistence
+        .load()
+        .expect("Failed to load persisted rooms.");
+
+    // Countdown timers don't survive a restart; don't leave rooms stuck mid-countdown.
+    // Nobody is connected yet either.
+    for room in restored_rooms.iter_mut() {
+        if room.is_countdown_running() {
+            let _ = room.cancel_countdown(None);
+        }
+        for user in room.users.iter_mut() {
+            user.online = false;
+        }
+    }
+    persistence
+        .compact(&restored_rooms)
+        .expect("Failed to compact persisted rooms.");
+
+    let repository: Repository = Arc::new(InMemoryRoomRepository::with_rooms(
+        persistence.clone(),
+        restored_rooms,
+    ));
 
     // ----- Prometheus setup -----
     let registry = Registry::new();
@@ -393,11 +456,31 @@ async fn main() -> std::io::Result<()> {
     registry.register(Box::new(total_room_bytes_gauge.clone())).ok();
     registry.register(Box::new(avg_room_bytes_gauge.clone())).ok();
 
+    // Broker slow-consumer counters, per subscription type
+    let broker_metrics = BrokerMetrics {
+        dropped: IntCounterVec::new(
+            Opts::new("broker_messages_dropped_total", "Messages dropped from full subscriber queues"),
+            &["subscription"],
+        ).unwrap(),
+        coalesced: IntCounterVec::new(
+            Opts::new("broker_messages_coalesced_total", "Queued room snapshots replaced by a newer one"),
+            &["subscription"],
+        ).unwrap(),
+        disconnected: IntCounterVec::new(
+            Opts::new("broker_subscribers_disconnected_total", "Subscriptions ended for falling behind"),
+            &["subscription"],
+        ).unwrap(),
+    };
+    registry.register(Box::new(broker_metrics.dropped.clone())).ok();
+    registry.register(Box::new(broker_metrics.coalesced.clone())).ok();
+    registry.register(Box::new(broker_metrics.disconnected.clone())).ok();
+    simple_broker::configure(settings.broker.clone(), broker_metrics);
+
     let registry = Arc::new(registry);
 
     // Spawn the cleanup task: every 30 minutes, remove rooms inactive > 8 days
     spawn_room_cleanup_task(
-        storage.clone(),
+        repository.clone(),
         StdDuration::from_secs(60 * 30),
         StdDuration::from_secs(8 * 24 * 60 * 60),
         rooms_evicted.clone(),
@@ -414,7 +497,7 @@ async fn main() -> std::io::Result<()> {
         .unwrap_or(60);
 
     spawn_heartbeat_task(
-        storage.clone(),
+        repository.clone(),
         StdDuration::from_secs(hb_interval_secs),
         total_users.clone(),
         avg_users_per_room.clone(),
@@ -428,8 +511,38 @@ async fn main() -> std::io::Result<()> {
         avg_room_bytes_gauge.clone(),
     );
 
+    // Only reachable locally: elsewhere the configuration requires a secret.
+    let signer = match settings.auth.session_secret.as_deref().filter(|secret| !secret.is_empty()) {
+        Some(secret) => SessionSigner::new(secret.as_bytes()),
+        None => {
+            warn!("No auth.session_secret configured; using a random one, sessions won't survive a restart");
+            SessionSigner::random()
+        }
+    };
+
+    let admin = AdminCredential::new(settings.auth.admin_token.as_deref());
+
+    let presence = PresenceTracker::new(
+        repository.clone(),
+        settings.presence.disconnect_grace_seconds.map(StdDuration::from_secs),
+    );
+    presence.expect_reconnects().await;
+
+    // Auto-reset timers don't survive a restart; start them over for revealed rounds.
+    let scheduler = RoomScheduler::new(repository.clone());
+    repository
+        .for_each(&mut |room| {
+            if room.is_game_over {
+                scheduler.on_revealed(room);
+            }
+        })
+        .await;
+
     let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
-        .data(storage.clone())
+        .data(repository.clone())
+        .data(scheduler)
+        .data(presence)
+        .data(signer.clone())
         .finish();
 
     HttpServer::new(move || {
@@ -437,6 +550,9 @@ async fn main() -> std::io::Result<()> {
 
         App::new()
             .app_data(Data::new(schema.clone()))
+            .app_data(Data::new(signer.clone()))
+            .app_data(Data::new(admin.clone()))
+            .app_data(Data::new(repository.clone()))
             .wrap(Cors::permissive())
             .wrap(middleware::Logger::default())
             .service(
@@ -461,6 +577,11 @@ async fn main() -> std::io::Result<()> {
                     .to(index_ws),
             )
             .service(web::resource("/").guard(guard::Get()).to(index_playground))
+            .service(
+                web::resource("/rooms/{room_id}/report.{format}")
+                    .guard(guard::Get())
+                    .to(room_report),
+            )
             .service(
                 web::resource("/health_check")
                     .guard(guard::Get())
@@ -0,0 +1,318 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+};
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::room::Room, types::EntityId};
+
+/// Durable backing store for rooms.
+///
+/// The in-memory map stays the source of truth while the server is running;
+/// a `RoomStore` only receives write-through updates and is read back once on
+/// startup.
+pub trait RoomStore: Send + Sync {
+    /// Load every persisted room.
+    fn load(&self) -> io::Result<Vec<Room>>;
+
+    /// Persist the latest state of a room.
+    fn save(&self, room: &Room) -> io::Result<()>;
+
+    /// Forget a room (e.g. after it expired).
+    fn delete(&self, room_id: EntityId) -> io::Result<()>;
+
+    /// Rewrite the store so it only contains `rooms`, followed by whatever
+    /// was written since the last [`RoomStore::rotate`].
+    fn compact(&self, rooms: &[Room]) -> io::Result<()>;
+
+    /// Set aside the writes from here on, so a `compact` with rooms copied
+    /// after this call keeps any of them the copies missed.
+    fn rotate(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// No-op store: rooms live in memory only.
+pub struct MemoryStore;
+
+impl RoomStore for MemoryStore {
+    fn load(&self) -> io::Result<Vec<Room>> {
+        Ok(Vec::new())
+    }
+
+    fn save(&self, _room: &Room) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn delete(&self, _room_id: EntityId) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn compact(&self, _rooms: &[Room]) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
+enum LogEntry<'a> {
+    Upsert { room: Cow<'a, Room> },
+    Delete { room_id: EntityId },
+}
+
+/// Work for the writer thread, handled in the order it was queued.
+enum Command {
+    Append(Vec<u8>),
+    Rotate {
+        done: Sender<io::Result<()>>,
+    },
+    Compact {
+        snapshot: Vec<u8>,
+        done: Sender<io::Result<()>>,
+    },
+}
+
+/// Append-only JSON-lines log of room snapshots.
+///
+/// Every write appends one line; replaying the file keeps the last snapshot of
+/// each room. The log is compacted on startup and periodically by the cleanup
+/// task so it doesn't grow without bound.
+///
+/// A line is the whole room, including up to 100 chat messages, so between
+/// compactions the log grows by one room snapshot per vote, chat message or
+/// other saved change. For a busy room with a full chat history that is tens
+/// of kilobytes per change, for the 30 minutes between cleanup ticks.
+///
+/// Callers only serialize the entry and queue it: a dedicated writer thread
+/// owns the file and does all the I/O, so saving a room never blocks the
+/// async runtime. A failed write is logged by the writer.
+///
+/// Between a rotation and the next compaction, writes go to a separate tail
+/// file next to the log; loading replays the log, then the tail.
+pub struct AppendOnlyFileStore {
+    path: PathBuf,
+    commands: Sender<Command>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl AppendOnlyFileStore {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = Self::open_for_append(&path)?;
+        let (commands, queue) = mpsc::channel();
+
+        let writer = {
+            let path = path.clone();
+            thread::Builder::new()
+                .name("room-store".to_string())
+                .spawn(move || Self::run_writer(path, file, queue))?
+        };
+
+        Ok(AppendOnlyFileStore {
+            path,
+            commands,
+            writer: Some(writer),
+        })
+    }
+
+    fn open_for_append(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    fn append(&self, entry: &LogEntry<'_>) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry).map_err(io::Error::other)?;
+        line.push(b'\n');
+
+        self.send(Command::Append(line))
+    }
+
+    fn send(&self, command: Command) -> io::Result<()> {
+        self.commands
+            .send(command)
+            .map_err(|_| io::Error::other("room store writer has stopped"))
+    }
+
+    fn tail_path(path: &Path) -> PathBuf {
+        let mut tail = path.as_os_str().to_owned();
+        tail.push(".tail");
+        PathBuf::from(tail)
+    }
+
+    fn run_writer(path: PathBuf, mut file: File, queue: Receiver<Command>) {
+        let tail_path = Self::tail_path(&path);
+        let mut rotated = false;
+
+        // Runs until the store is dropped and everything queued has been written.
+        for command in queue {
+            match command {
+                Command::Append(line) => {
+                    if let Err(err) = file.write_all(&line) {
+                        error!("persistence: failed to append to {}: {}", path.display(), err);
+                    }
+                }
+                Command::Rotate { done } if rotated => {
+                    let _ = done.send(Ok(()));
+                }
+                Command::Rotate { done } => {
+                    // Writes keep going to the log; the caller must not compact.
+                    let result = Self::open_for_append(&tail_path).map(|tail| {
+                        file = tail;
+                        rotated = true;
+                    });
+                    let _ = done.send(result);
+                }
+                Command::Compact { snapshot, done } => {
+                    let result = Self::rewrite(&path, &tail_path, &snapshot, rotated).and_then(|_| {
+                        file = Self::open_for_append(&path)?;
+                        rotated = false;
+                        Ok(())
+                    });
+                    let _ = done.send(result);
+                }
+            }
+        }
+    }
+
+    /// Atomically replace the log with `snapshot`, plus the tail if the writes
+    /// were rotated into it, and drop the tail.
+    fn rewrite(path: &Path, tail_path: &Path, snapshot: &[u8], rotated: bool) -> io::Result<()> {
+        let tmp_path = path.with_extension("compact");
+        {
+            let mut file = File::create(&tmp_path)?;
+            file.write_all(snapshot)?;
+            if rotated {
+                io::copy(&mut File::open(tail_path)?, &mut file)?;
+            }
+            file.sync_all()?;
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        // Replaying a tail that is already folded into the log changes
+        // nothing, so a crash before this point loses no data.
+        match fs::remove_file(tail_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn replay(path: &Path, rooms: &mut HashMap<EntityId, Room>) -> io::Result<()> {
+        let file = File::open(path)?;
+
+        for (line_no, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // A crash mid-write can leave a truncated last line; skip anything unreadable.
+            match serde_json::from_str::<LogEntry>(&line) {
+                Ok(LogEntry::Upsert { room }) => {
+                    let room = room.into_owned();
+                    rooms.insert(room.id, room);
+                }
+                Ok(LogEntry::Delete { room_id }) => {
+                    rooms.remove(&room_id);
+                }
+                Err(err) => {
+                    warn!(
+                        "persistence: skipping unreadable entry at {}:{} ({})",
+                        path.display(),
+                        line_no + 1,
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for AppendOnlyFileStore {
+    fn drop(&mut self) {
+        // Closing the queue lets the writer drain it and stop; wait so nothing
+        // queued is lost on shutdown.
+        self.commands = mpsc::channel().0;
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+impl RoomStore for AppendOnlyFileStore {
+    fn load(&self) -> io::Result<Vec<Room>> {
+        let mut rooms: HashMap<EntityId, Room> = HashMap::new();
+
+        Self::replay(&self.path, &mut rooms)?;
+
+        // Left over from a compaction that never finished.
+        let tail_path = Self::tail_path(&self.path);
+        if tail_path.exists() {
+            Self::replay(&tail_path, &mut rooms)?;
+        }
+
+        info!(
+            "persistence: loaded {} room(s) from {}",
+            rooms.len(),
+            self.path.display()
+        );
+
+        Ok(rooms.into_values().collect())
+    }
+
+    fn save(&self, room: &Room) -> io::Result<()> {
+        self.append(&LogEntry::Upsert {
+            room: Cow::Borrowed(room),
+        })
+    }
+
+    fn delete(&self, room_id: EntityId) -> io::Result<()> {
+        self.append(&LogEntry::Delete { room_id })
+    }
+
+    fn compact(&self, rooms: &[Room]) -> io::Result<()> {
+        let mut snapshot = Vec::new();
+        for room in rooms {
+            serde_json::to_writer(
+                &mut snapshot,
+                &LogEntry::Upsert {
+                    room: Cow::Borrowed(room),
+                },
+            )
+            .map_err(io::Error::other)?;
+            snapshot.push(b'\n');
+        }
+
+        // The writer swaps files between two queued writes, so none is lost;
+        // wait for it to report how the rewrite went.
+        let (done, result) = mpsc::channel();
+        self.send(Command::Compact { snapshot, done })?;
+        result
+            .recv()
+            .map_err(|_| io::Error::other("room store writer has stopped"))?
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        let (done, result) = mpsc::channel();
+        self.send(Command::Rotate { done })?;
+        result
+            .recv()
+            .map_err(|_| io::Error::other("room store writer has stopped"))?
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures_util::Stream;
+use log::{debug, warn};
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, Duration},
+};
+
+use crate::{
+    domain::{
+        room_change::{RoomChange, RoomDelta},
+        room_event::{RoomEvent, RoomEventPayload, UserOfflinePayload, UserOnlinePayload, UserTimedOutPayload},
+    },
+    types::{EntityId, Repository},
+};
+
+type PresenceKey = (EntityId, EntityId);
+
+/// Tracks which users have a live subscription to which room.
+///
+/// Every room subscription of an authenticated user holds a [`PresenceGuard`].
+/// The user is online while they hold at least one, and goes offline when the
+/// last one is dropped (the WebSocket closed or the client unsubscribed). With
+/// a grace period configured, a user who stays offline that long is removed
+/// from the room along with their card.
+///
+/// Room updates re-read the connection count under the room lock, so updates
+/// racing each other always settle on the actual state.
+pub struct PresenceTracker {
+    repository: Repository,
+    grace_period: Option<Duration>,
+    connections: Mutex<HashMap<PresenceKey, usize>>,
+    removals: Mutex<HashMap<PresenceKey, JoinHandle<()>>>,
+}
+
+impl PresenceTracker {
+    pub fn new(repository: Repository, grace_period: Option<Duration>) -> Arc<Self> {
+        Arc::new(PresenceTracker {
+            repository,
+            grace_period,
+            connections: Mutex::new(HashMap::new()),
+            removals: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn is_connected(&self, room_id: EntityId, user_id: EntityId) -> bool {
+        self.connections
+            .lock()
+            .unwrap()
+            .contains_key(&(room_id, user_id))
+    }
+
+    /// Count a new connection of `user_id` to `room_id` until the guard is dropped.
+    pub fn connect(self: &Arc<Self>, room_id: EntityId, user_id: EntityId) -> PresenceGuard {
+        let key = (room_id, user_id);
+
+        let first = {
+            let mut connections = self.connections.lock().unwrap();
+            let count = connections.entry(key).or_insert(0);
+            *count += 1;
+            *count == 1
+        };
+
+        if first {
+            if let Some(removal) = self.removals.lock().unwrap().remove(&key) {
+                removal.abort();
+            }
+
+            let tracker = Arc::clone(self);
+            tokio::spawn(async move { tracker.sync(room_id, user_id).await });
+        }
+
+        PresenceGuard {
+            tracker: Arc::clone(self),
+            room_id,
+            user_id,
+        }
+    }
+
+    fn disconnect(self: &Arc<Self>, room_id: EntityId, user_id: EntityId) {
+        let key = (room_id, user_id);
+
+        let last = {
+            let mut connections = self.connections.lock().unwrap();
+            match connections.get_mut(&key) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    false
+                }
+                Some(_) => {
+                    connections.remove(&key);
+                    true
+                }
+                None => false,
+            }
+        };
+
+        if !last {
+            return;
+        }
+
+        // Dropped outside a runtime (e.g. at shutdown): nothing left to notify.
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        self.await_reconnect(&runtime, room_id, user_id, true);
+    }
+
+    /// Users restored at startup are all offline, with no connection left to
+    /// drop; start their grace period as if they had just disconnected.
+    pub async fn expect_reconnects(self: &Arc<Self>) {
+        if self.grace_period.is_none() {
+            return;
+        }
+
+        let mut offline = Vec::new();
+        self.repository
+            .for_each(&mut |room| {
+                for user in room.users.iter().filter(|user| !user.online) {
+                    offline.push((room.id, user.id));
+                }
+            })
+            .await;
+
+        let runtime = tokio::runtime::Handle::current();
+        for (room_id, user_id) in offline {
+            if !self.is_connected(room_id, user_id) {
+                self.await_reconnect(&runtime, room_id, user_id, false);
+            }
+        }
+    }
+
+    /// Remove the user once the grace period passes without a reconnect,
+    /// first marking them offline if `sync` is set.
+    fn await_reconnect(
+        self: &Arc<Self>,
+        runtime: &tokio::runtime::Handle,
+        room_id: EntityId,
+        user_id: EntityId,
+        sync: bool,
+    ) {
+        let key = (room_id, user_id);
+        let tracker = Arc::clone(self);
+
+        // Register under the lock so the task can't finish and `forget` itself first.
+        let mut removals = self.removals.lock().unwrap();
+
+        let handle = runtime.spawn(async move {
+            if sync {
+                tracker.sync(room_id, user_id).await;
+            }
+
+            if let Some(grace_period) = tracker.grace_period {
+                sleep(grace_period).await;
+                tracker.remove_if_gone(room_id, user_id).await;
+            }
+
+            tracker.forget(key);
+        });
+
+        if let Some(previous) = removals.insert(key, handle) {
+            previous.abort();
+        }
+    }
+
+    /// Bring the user's `online` flag in line with their connections.
+    ///
+    /// Presence is runtime state (a restart marks everyone offline) and not
+    /// room activity, so it is neither persisted nor keeps the room alive.
+    async fn sync(&self, room_id: EntityId, user_id: EntityId) {
+        let result = self
+            .repository
+            .update_transient_with(room_id, |room| {
+                let online = self.is_connected(room_id, user_id);
+                if !room.set_online(user_id, online) {
+                    return Ok(());
+                }
+
+                room.bump_version();
+                RoomChange::publish(room, |room| RoomDelta::user_updated(room, user_id));
+                RoomEvent::publish(room, |room| {
+                    if online {
+                        RoomEventPayload::UserOnline(UserOnlinePayload { user_id })
+                    } else {
+                        RoomEventPayload::UserOffline(UserOfflinePayload {
+                            user_id,
+                            last_seen: room
+                                .users
+                                .iter()
+                                .find(|user| user.id == user_id)
+                                .and_then(|user| user.last_seen),
+                        })
+                    }
+                });
+                Ok(())
+            })
+            .await;
+
+        // The room may have expired meanwhile; there's no one to tell then.
+        if let Err(err) = result {
+            debug!("presence: could not update room {}: {}", room_id, err.message);
+        }
+    }
+
+    async fn remove_if_gone(&self, room_id: EntityId, user_id: EntityId) {
+        let result = self
+            .repository
+            .update_with(room_id, |room| {
+                if self.is_connected(room_id, user_id) || !room.is_user_exist(user_id) {
+                    return Ok(());
+                }
+
+                room.remove_user(user_id);
+                room.touch();
+                RoomChange::publish(room, |_| RoomDelta::user_left(user_id, false));
+                RoomEvent::publish(room, |_| {
+                    RoomEventPayload::UserTimedOut(UserTimedOutPayload { user_id })
+                });
+                Ok(())
+            })
+            .await;
+
+        if let Err(err) = result {
+            warn!("presence: could not remove user {} from room {}: {}", user_id, room_id, err.message);
+        }
+    }
+
+    fn forget(&self, key: PresenceKey) {
+        let mut removals = self.removals.lock().unwrap();
+
+        // A reconnect may already have replaced or dropped our entry.
+        if removals
+            .get(&key)
+            .is_some_and(|handle| Some(handle.id()) == tokio::task::try_id())
+        {
+            removals.remove(&key);
+        }
+    }
+}
+
+/// One live connection of a user to a room; see [`PresenceTracker::connect`].
+pub struct PresenceGuard {
+    tracker: Arc<PresenceTracker>,
+    room_id: EntityId,
+    user_id: EntityId,
+}
+
+impl Drop for PresenceGuard {
+    fn drop(&mut self) {
+        self.tracker.disconnect(self.room_id, self.user_id);
+    }
+}
+
+/// A subscription stream that keeps its subscriber counted as online.
+pub struct Tracked<S> {
+    stream: S,
+    _guard: Option<PresenceGuard>,
+}
+
+impl<S> Tracked<S> {
+    pub fn new(stream: S, guard: Option<PresenceGuard>) -> Self {
+        Tracked {
+            stream,
+            _guard: guard,
+        }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for Tracked<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
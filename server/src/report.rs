@@ -0,0 +1,492 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Write};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    domain::{room::Room, statistics::VoteStatistics, story::StoryStatus},
+    types::{Card, EntityId},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Parse the extension of `report.<ext>`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "csv" => Some(ReportFormat::Csv),
+            "json" => Some(ReportFormat::Json),
+            "md" => Some(ReportFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Markdown => "md",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "text/csv; charset=utf-8",
+            ReportFormat::Json => "application/json",
+            ReportFormat::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+}
+
+/// Everything that happened in a room, flattened for export.
+#[derive(Debug, Serialize)]
+pub struct SessionReport {
+    pub room_id: EntityId,
+    pub room_name: Option<String>,
+    pub generated_at: DateTime<Utc>,
+    pub deck: Vec<Card>,
+    pub participants: Vec<ReportParticipant>,
+    pub stories: Vec<ReportStory>,
+    pub rounds: Vec<ReportRound>,
+    pub chat: Vec<ReportChatMessage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportParticipant {
+    pub user_id: EntityId,
+    pub username: String,
+    pub is_owner: bool,
+    pub is_spectator: bool,
+    /// The card on the table, only once the current round is revealed.
+    pub vote: Option<Card>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportStory {
+    pub title: String,
+    pub external_key: Option<String>,
+    pub status: StoryStatus,
+    pub final_estimate: Option<Card>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportRound {
+    pub number: u32,
+    pub story_title: Option<String>,
+    pub revealed_at: DateTime<Utc>,
+    pub revealed_by: Option<String>,
+    pub votes: Vec<ReportVote>,
+    pub statistics: Option<VoteStatistics>,
+    pub final_estimate: Option<Card>,
+    pub final_estimate_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportVote {
+    pub username: String,
+    pub card: Option<Card>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportChatMessage {
+    pub timestamp: DateTime<Utc>,
+    pub username: String,
+    pub content: String,
+}
+
+impl SessionReport {
+    pub fn from_room(room: &Room) -> Self {
+        // Users who left are only known from their chat messages.
+        let mut usernames: HashMap<EntityId, String> = room
+            .chat_history
+            .iter()
+            .map(|msg| (msg.user_id, msg.username.clone()))
+            .collect();
+        usernames.extend(
+            room.users
+                .iter()
+                .map(|user| (user.id, user.username.clone())),
+        );
+
+        let username = |id: EntityId| {
+            usernames
+                .get(&id)
+                .cloned()
+                .unwrap_or_else(|| id.to_string())
+        };
+
+        let participants = room
+            .users
+            .iter()
+            .map(|user| ReportParticipant {
+                user_id: user.id,
+                username: user.username.clone(),
+                is_owner: room.room_owner_id == Some(user.id),
+                is_spectator: user.is_spectator,
+                vote: room
+                    .is_game_over
+                    .then(|| room.game.table.iter().find(|uc| uc.user_id == user.id))
+                    .flatten()
+                    .and_then(|uc| uc.card.clone()),
+            })
+            .collect();
+
+        let rounds = room
+            .round_history
+            .iter()
+            .map(|round| ReportRound {
+                number: round.number,
+                story_title: round.story_title.clone(),
+                revealed_at: round.revealed_at,
+                revealed_by: round.revealed_by.map(username),
+                votes: round
+                    .votes
+                    .iter()
+                    .map(|vote| ReportVote {
+                        username: username(vote.user_id),
+                        card: vote.card.clone(),
+                    })
+                    .collect(),
+                statistics: round.statistics.clone(),
+                final_estimate: round.final_estimate.clone(),
+                final_estimate_at: round.final_estimate_at,
+            })
+            .collect();
+
+        SessionReport {
+            room_id: room.id,
+            room_name: room.name.clone(),
+            generated_at: Utc::now(),
+            deck: room.deck.labels(),
+            participants,
+            stories: room
+                .stories
+                .iter()
+                .map(|story| ReportStory {
+                    title: story.title.clone(),
+                    external_key: story.external_key.clone(),
+                    status: story.status,
+                    final_estimate: story.final_estimate.clone(),
+                })
+                .collect(),
+            rounds,
+            chat: room
+                .chat_history
+                .iter()
+                .map(|msg| ReportChatMessage {
+                    timestamp: msg.timestamp,
+                    username: msg.username.clone(),
+                    content: msg.content.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// One record per line, tagged by `section` so it stays a single sheet.
+    fn to_csv(&self) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let mut row = |fields: [&str; 6]| {
+            // Writing to a Vec can't fail.
+            let fields = fields.map(defuse_formula);
+            let _ = writer.write_record(fields.iter().map(|field| field.as_bytes()));
+        };
+
+        row(["section", "round", "story", "user", "value", "timestamp"]);
+
+        for card in &self.deck {
+            row(["deck", "", "", "", card, ""]);
+        }
+
+        for participant in &self.participants {
+            row([
+                "participant",
+                "",
+                "",
+                &participant.username,
+                participant.vote.as_deref().unwrap_or(""),
+                "",
+            ]);
+        }
+
+        for story in &self.stories {
+            row([
+                "story",
+                "",
+                &story.title,
+                "",
+                story.final_estimate.as_deref().unwrap_or(""),
+                "",
+            ]);
+        }
+
+        for round in &self.rounds {
+            let number = round.number.to_string();
+            let revealed_at = round.revealed_at.to_rfc3339();
+            for vote in &round.votes {
+                row([
+                    "vote",
+                    &number,
+                    round.story_title.as_deref().unwrap_or(""),
+                    &vote.username,
+                    vote.card.as_deref().unwrap_or(""),
+                    &revealed_at,
+                ]);
+            }
+
+            if let Some(estimate) = &round.final_estimate {
+                let estimated_at = round
+                    .final_estimate_at
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default();
+                row([
+                    "estimate",
+                    &number,
+                    round.story_title.as_deref().unwrap_or(""),
+                    "",
+                    estimate,
+                    &estimated_at,
+                ]);
+            }
+        }
+
+        for msg in &self.chat {
+            row([
+                "chat",
+                "",
+                "",
+                &msg.username,
+                &msg.content,
+                &msg.timestamp.to_rfc3339(),
+            ]);
+        }
+
+        writer
+            .into_inner()
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default()
+    }
+
+    fn to_markdown(&self) -> String {
+        // `write!` into a String can't fail, hence the ignored results.
+        let mut out = String::new();
+        let title = self
+            .room_name
+            .as_deref()
+            .unwrap_or("Planning poker session");
+
+        let _ = writeln!(out, "# {}\n", escape(title));
+        let _ = writeln!(out, "Generated {}\n", self.generated_at.to_rfc3339());
+        let _ = writeln!(
+            out,
+            "**Deck:** {}\n",
+            self.deck
+                .iter()
+                .map(|c| escape(c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let _ = writeln!(out, "## Participants\n");
+        let _ = writeln!(out, "| User | Vote |\n| --- | --- |");
+        for participant in &self.participants {
+            let roles = match (participant.is_owner, participant.is_spectator) {
+                (true, true) => " (owner, spectator)",
+                (true, false) => " (owner)",
+                (false, true) => " (spectator)",
+                (false, false) => "",
+            };
+            let _ = writeln!(
+                out,
+                "| {}{} | {} |",
+                escape(&participant.username),
+                roles,
+                escape(participant.vote.as_deref().unwrap_or("-"))
+            );
+        }
+
+        if !self.stories.is_empty() {
+            let _ = writeln!(out, "\n## Stories\n");
+            let _ = writeln!(
+                out,
+                "| Story | Key | Status | Estimate |\n| --- | --- | --- | --- |"
+            );
+            for story in &self.stories {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {:?} | {} |",
+                    escape(&story.title),
+                    escape(story.external_key.as_deref().unwrap_or("-")),
+                    story.status,
+                    escape(story.final_estimate.as_deref().unwrap_or("-"))
+                );
+            }
+        }
+
+        let _ = writeln!(out, "\n## Rounds\n");
+        if self.rounds.is_empty() {
+            let _ = writeln!(out, "No rounds were revealed.");
+        }
+        for round in &self.rounds {
+            let story = round.story_title.as_deref().unwrap_or("Untitled");
+            let _ = writeln!(out, "### Round {}: {}\n", round.number, escape(story));
+            let _ = writeln!(
+                out,
+                "Revealed {}{}\n",
+                round.revealed_at.to_rfc3339(),
+                round
+                    .revealed_by
+                    .as_ref()
+                    .map(|name| format!(" by {}", escape(name)))
+                    .unwrap_or_default()
+            );
+            let _ = writeln!(out, "| User | Vote |\n| --- | --- |");
+            for vote in &round.votes {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} |",
+                    escape(&vote.username),
+                    escape(vote.card.as_deref().unwrap_or("-"))
+                );
+            }
+            if let Some(stats) = &round.statistics
+                && let Some(mean) = stats.mean
+            {
+                let _ = writeln!(
+                    out,
+                    "\nAverage {:.2}, median {:.2}{}",
+                    mean,
+                    stats.median.unwrap_or(mean),
+                    if stats.consensus { ", consensus" } else { "" }
+                );
+            }
+            if let Some(estimate) = &round.final_estimate {
+                let _ = writeln!(out, "\n**Agreed estimate:** {}", escape(estimate));
+            }
+            let _ = writeln!(out);
+        }
+
+        if !self.chat.is_empty() {
+            let _ = writeln!(out, "## Chat\n");
+            for msg in &self.chat {
+                let _ = writeln!(
+                    out,
+                    "- **{}** ({}): {}",
+                    escape(&msg.username),
+                    msg.timestamp.format("%Y-%m-%d %H:%M"),
+                    escape(&msg.content)
+                );
+            }
+        }
+
+        out
+    }
+}
+
+/// Spreadsheets run a cell that starts like a formula; a leading `'` keeps it text.
+fn defuse_formula(field: &str) -> Cow<'_, str> {
+    if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        Cow::Owned(format!("'{field}"))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Keep user text from breaking Markdown tables and formatting. Line breaks
+/// become spaces, as chat messages always did, so text stays on its line.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\n' | '\r') {
+            escaped.push(' ');
+            continue;
+        }
+        if matches!(
+            c,
+            '|' | '*' | '_' | '`' | '#' | '[' | ']' | '<' | '>' | '\\'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOSTILE: &str = "=HYPERLINK(\"http://evil\")|x\n# Owned";
+
+    fn report() -> SessionReport {
+        let user_id = EntityId::new_v4();
+        SessionReport {
+            room_id: EntityId::new_v4(),
+            room_name: Some(HOSTILE.to_string()),
+            generated_at: Utc::now(),
+            deck: vec!["-1".to_string(), "+1".to_string()],
+            participants: vec![ReportParticipant {
+                user_id,
+                username: "@admin".to_string(),
+                is_owner: true,
+                is_spectator: false,
+                vote: None,
+            }],
+            stories: vec![ReportStory {
+                title: HOSTILE.to_string(),
+                external_key: None,
+                status: StoryStatus::Pending,
+                final_estimate: None,
+            }],
+            rounds: vec![],
+            chat: vec![ReportChatMessage {
+                timestamp: Utc::now(),
+                username: "@admin".to_string(),
+                content: HOSTILE.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn csv_cells_never_start_a_formula() {
+        let csv = report().render(ReportFormat::Csv);
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+
+        let cells: Vec<String> = reader
+            .records()
+            .flat_map(|record| record.unwrap().iter().map(String::from).collect::<Vec<_>>())
+            .collect();
+        assert!(cells.contains(&"'-1".to_string()));
+        assert!(cells.contains(&"'@admin".to_string()));
+        assert!(cells.contains(&format!("'{HOSTILE}")));
+        assert!(cells.iter().all(|cell| !cell.starts_with(['=', '+', '-', '@'])));
+    }
+
+    #[test]
+    fn markdown_keeps_user_text_on_its_line() {
+        let markdown = report().render(ReportFormat::Markdown);
+
+        // Only the report's own title is a heading, and the story is one table row.
+        assert_eq!(markdown.lines().filter(|line| line.starts_with("# ")).count(), 1);
+        assert!(!markdown.lines().any(|line| line.starts_with("# Owned")));
+        let story_row = markdown
+            .lines()
+            .find(|line| line.contains("HYPERLINK") && line.ends_with("| Pending | - |"));
+        assert_eq!(
+            story_row,
+            Some("| =HYPERLINK(\"http://evil\")\\|x \\# Owned | - | Pending | - |")
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::{collections::HashMap, io, sync::Arc};
+
+use async_graphql::{Error, Result};
+use async_trait::async_trait;
+use log::warn;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{
+    domain::{error::RoomError, room::Room},
+    types::{EntityId, Persistence},
+};
+
+/// A mutation applied to a single room while the repository holds it.
+///
+/// Returning an error aborts the update: the room is left as it was and
+/// nothing is persisted.
+pub type RoomMutation<'a> = Box<dyn FnOnce(&mut Room) -> Result<()> + Send + 'a>;
+
+/// Access to the set of live rooms.
+///
+/// Resolvers go through this trait instead of touching the underlying map, so
+/// the storage strategy (in-memory, persistent, sharded, ...) can be swapped
+/// without rewriting them.
+#[async_trait]
+pub trait RoomRepository: Send + Sync {
+    /// Snapshot of a single room.
+    async fn get(&self, room_id: EntityId) -> Option<Room>;
+
+    /// Insert a new room.
+    ///
+    /// Fails with [`RoomError::RoomExists`] if there already is a room with its ID.
+    async fn insert(&self, room: Room) -> Result<()>;
+
+    /// Remove a room and return it.
+    async fn remove(&self, room_id: EntityId) -> Option<Room>;
+
+    /// Apply `mutation` to a room while holding it.
+    ///
+    /// Fails with [`RoomError::RoomNotFound`] if there is no such room.
+    async fn update(&self, room_id: EntityId, mutation: RoomMutation<'_>) -> Result<()>;
+
+    /// Like [`RoomRepository::update`], for runtime-only changes (countdown
+    /// ticks, presence) that aren't written to the backing store.
+    async fn update_transient(&self, room_id: EntityId, mutation: RoomMutation<'_>) -> Result<()>;
+
+    /// Visit every room without cloning it.
+    async fn for_each(&self, visitor: &mut (dyn for<'r> FnMut(&'r Room) + Send));
+
+    /// Snapshot of every room.
+    async fn all(&self) -> Vec<Room>;
+
+    /// Snapshot of every room the user is currently in.
+    async fn find_by_user(&self, user_id: EntityId) -> Vec<Room>;
+
+    /// Whether a user is in a room; `false` if there is no such room.
+    async fn is_member(&self, room_id: EntityId, user_id: EntityId) -> bool {
+        self.get(room_id).await.is_some_and(|room| room.is_user_exist(user_id))
+    }
+
+    /// Number of rooms.
+    async fn count(&self) -> usize;
+
+    /// Give the backing store a chance to rewrite itself from the live rooms.
+    async fn compact(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl dyn RoomRepository {
+    /// Like [`RoomRepository::update`], but hands back whatever the closure returns.
+    pub async fn update_with<R, F>(&self, room_id: EntityId, f: F) -> Result<R>
+    where
+        R: Send,
+        F: FnOnce(&mut Room) -> Result<R> + Send,
+    {
+        self.apply_with(room_id, f, true).await
+    }
+
+    /// Like [`RoomRepository::update_transient`], but hands back whatever the closure returns.
+    pub async fn update_transient_with<R, F>(&self, room_id: EntityId, f: F) -> Result<R>
+    where
+        R: Send,
+        F: FnOnce(&mut Room) -> Result<R> + Send,
+    {
+        self.apply_with(room_id, f, false).await
+    }
+
+    async fn apply_with<R, F>(&self, room_id: EntityId, f: F, persist: bool) -> Result<R>
+    where
+        R: Send,
+        F: FnOnce(&mut Room) -> Result<R> + Send,
+    {
+        let mut output = None;
+
+        let mutation: RoomMutation<'_> = Box::new(|room| {
+            output = Some(f(room)?);
+            Ok(())
+        });
+
+        if persist {
+            self.update(room_id, mutation).await?;
+        } else {
+            self.update_transient(room_id, mutation).await?;
+        }
+
+        Ok(output.expect("room mutation completed without producing a value"))
+    }
+}
+
+/// A single room behind its own lock.
+///
+/// The slot is emptied when the room is removed, so a mutation that looked the
+/// room up just before removal can't resurrect it.
+type RoomSlot = Arc<Mutex<Option<Room>>>;
+
+/// Rooms kept in memory, written through to a [`RoomStore`](crate::persistence::RoomStore).
+///
+/// Every room has its own lock; the outer index is only write-locked to add or
+/// remove rooms, so activity in one room never waits on another.
+pub struct InMemoryRoomRepository {
+    rooms: RwLock<HashMap<EntityId, RoomSlot>>,
+    store: Persistence,
+}
+
+impl InMemoryRoomRepository {
+    pub fn with_rooms(store: Persistence, rooms: Vec<Room>) -> Self {
+        InMemoryRoomRepository {
+            rooms: RwLock::new(
+                rooms
+                    .into_iter()
+                    .map(|room| (room.id, Arc::new(Mutex::new(Some(room)))))
+                    .collect(),
+            ),
+            store,
+        }
+    }
+
+    fn persist(&self, room: &Room) {
+        // A failing disk shouldn't take the room down with it, so errors are only logged.
+        if let Err(err) = self.store.save(room) {
+            warn!("persistence: failed to save room {}: {}", room.id, err);
+        }
+    }
+
+    async fn apply(&self, room_id: EntityId, mutation: RoomMutation<'_>, persist: bool) -> Result<()> {
+        let not_found = || Error::from(RoomError::RoomNotFound);
+
+        let slot = self.slot(room_id).await.ok_or_else(not_found)?;
+        let mut guard = slot.lock().await;
+        let room = guard.as_mut().ok_or_else(not_found)?;
+
+        // Work on a copy so a mutation that fails halfway leaves no trace.
+        let mut updated = room.clone();
+        mutation(&mut updated)?;
+        *room = updated;
+
+        if persist {
+            self.persist(room);
+        }
+
+        Ok(())
+    }
+
+    async fn slot(&self, room_id: EntityId) -> Option<RoomSlot> {
+        self.rooms.read().await.get(&room_id).cloned()
+    }
+
+    async fn slots(&self) -> Vec<RoomSlot> {
+        self.rooms.read().await.values().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl RoomRepository for InMemoryRoomRepository {
+    async fn get(&self, room_id: EntityId) -> Option<Room> {
+        self.slot(room_id).await?.lock().await.clone()
+    }
+
+    async fn insert(&self, room: Room) -> Result<()> {
+        let mut index = self.rooms.write().await;
+        if index.contains_key(&room.id) {
+            return Err(RoomError::RoomExists.into());
+        }
+
+        self.persist(&room);
+        index.insert(room.id, Arc::new(Mutex::new(Some(room))));
+        Ok(())
+    }
+
+    async fn remove(&self, room_id: EntityId) -> Option<Room> {
+        let slot = self.rooms.write().await.remove(&room_id)?;
+        let removed = slot.lock().await.take();
+
+        if removed.is_some()
+            && let Err(err) = self.store.delete(room_id)
+        {
+            warn!("persistence: failed to delete room {}: {}", room_id, err);
+        }
+
+        removed
+    }
+
+    async fn update(&self, room_id: EntityId, mutation: RoomMutation<'_>) -> Result<()> {
+        self.apply(room_id, mutation, true).await
+    }
+
+    async fn update_transient(&self, room_id: EntityId, mutation: RoomMutation<'_>) -> Result<()> {
+        self.apply(room_id, mutation, false).await
+    }
+
+    async fn for_each(&self, visitor: &mut (dyn for<'r> FnMut(&'r Room) + Send)) {
+        for slot in self.slots().await {
+            if let Some(room) = slot.lock().await.as_ref() {
+                visitor(room);
+            }
+        }
+    }
+
+    async fn all(&self) -> Vec<Room> {
+        let mut rooms = Vec::new();
+        self.for_each(&mut |room| rooms.push(room.clone())).await;
+        rooms
+    }
+
+    async fn find_by_user(&self, user_id: EntityId) -> Vec<Room> {
+        let mut rooms = Vec::new();
+        self.for_each(&mut |room| {
+            if room.is_user_exist(user_id) {
+                rooms.push(room.clone());
+            }
+        })
+        .await;
+        rooms
+    }
+
+    async fn is_member(&self, room_id: EntityId, user_id: EntityId) -> bool {
+        match self.slot(room_id).await {
+            Some(slot) => slot.lock().await.as_ref().is_some_and(|room| room.is_user_exist(user_id)),
+            None => false,
+        }
+    }
+
+    async fn count(&self) -> usize {
+        self.rooms.read().await.len()
+    }
+
+    async fn compact(&self) -> io::Result<()> {
+        // Writes from here on are kept apart, so the copies below may be taken
+        // one room at a time while the rooms stay in use.
+        self.store.rotate()?;
+
+        let mut snapshot = Vec::new();
+        for slot in self.slots().await {
+            if let Some(room) = slot.lock().await.as_ref() {
+                snapshot.push(room.clone());
+            }
+        }
+
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || store.compact(&snapshot))
+            .await
+            .map_err(io::Error::other)?
+    }
+}
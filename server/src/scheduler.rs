@@ -0,0 +1,340 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use log::{debug, warn};
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, Duration},
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        room::Room,
+        room_change::{RoomChange, RoomDelta},
+        room_event::{RoomEvent, RoomEventPayload},
+    },
+    types::{EntityId, Repository},
+};
+
+/// Owns timed room transitions (the reveal countdown and the automatic reset
+/// after a reveal) so they run on the server, independent of the request that
+/// triggered them.
+///
+/// Each room has at most one pending timer, identified by the running
+/// `countdown_id` or the revealed `game.id`. Every step re-checks that ID
+/// under the room lock, so a cancelled or superseded timer can never act, and
+/// each transition happens exactly once.
+pub struct RoomScheduler {
+    repository: Repository,
+    timers: Mutex<HashMap<EntityId, Timer>>,
+}
+
+struct Timer {
+    id: Uuid,
+    handle: JoinHandle<()>,
+}
+
+impl RoomScheduler {
+    pub fn new(repository: Repository) -> Arc<Self> {
+        Arc::new(RoomScheduler {
+            repository,
+            timers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Drive the countdown `countdown_id` (already started on the room) to completion.
+    pub fn schedule_countdown(self: &Arc<Self>, room_id: EntityId, countdown_id: Uuid, seconds: i32) {
+        let scheduler = Arc::clone(self);
+        self.spawn_timer(room_id, countdown_id, async move {
+            scheduler.run_countdown(room_id, countdown_id, seconds).await;
+        });
+    }
+
+    /// Schedule whatever follows a reveal in `room` (currently the automatic reset).
+    pub fn on_revealed(self: &Arc<Self>, room: &Room) {
+        let Some(seconds) = room.auto_reset_seconds else {
+            return;
+        };
+
+        let scheduler = Arc::clone(self);
+        let room_id = room.id;
+        let game_id = room.game.id;
+
+        self.spawn_timer(room_id, game_id, async move {
+            sleep(Duration::from_secs(seconds.max(0) as u64)).await;
+            scheduler.run_auto_reset(room_id, game_id).await;
+        });
+    }
+
+    /// The ID of the timer `room` is waiting on, if any. Read it in the same
+    /// update that makes the timer obsolete, then pass it to [`Self::cancel`].
+    pub fn pending_timer_id(room: &Room) -> Option<Uuid> {
+        if room.is_countdown_running() {
+            room.countdown_id
+        } else if room.is_game_over && room.auto_reset_seconds.is_some() {
+            Some(room.game.id)
+        } else {
+            None
+        }
+    }
+
+    /// Stop the room's pending timer if it is still `timer_id`; a timer
+    /// started since is left alone.
+    pub fn cancel(&self, room_id: EntityId, timer_id: Uuid) {
+        let mut timers = self.timers.lock().unwrap();
+
+        if timers.get(&room_id).is_some_and(|timer| timer.id == timer_id)
+            && let Some(timer) = timers.remove(&room_id)
+        {
+            timer.handle.abort();
+        }
+    }
+
+    fn spawn_timer<F>(self: &Arc<Self>, room_id: EntityId, timer_id: Uuid, timer: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let scheduler = Arc::clone(self);
+
+        // Register under the lock so the task can't finish and `forget` itself first.
+        let mut timers = self.timers.lock().unwrap();
+
+        let handle = tokio::spawn(async move {
+            timer.await;
+            scheduler.forget(room_id);
+        });
+
+        // A countdown schedules the auto-reset from its own task; don't abort ourselves.
+        if let Some(previous) = timers.insert(room_id, Timer { id: timer_id, handle })
+            && Some(previous.handle.id()) != tokio::task::try_id()
+        {
+            previous.handle.abort();
+        }
+    }
+
+    async fn run_countdown(self: &Arc<Self>, room_id: EntityId, countdown_id: Uuid, seconds: i32) {
+        debug!("scheduler: countdown {} started in room {}", countdown_id, room_id);
+
+        for remaining in (1..seconds).rev() {
+            sleep(Duration::from_secs(1)).await;
+
+            // Ticks are runtime state; a restart cancels the countdown anyway.
+            let still_running = self
+                .repository
+                .update_transient_with(room_id, |room| {
+                    if !room.is_current_countdown(countdown_id) {
+                        return Ok(false);
+                    }
+
+                    room.update_countdown_value(remaining);
+                    room.touch();
+                    RoomChange::publish(room, RoomDelta::reveal_stage_changed);
+                    Ok(true)
+                })
+                .await
+                .unwrap_or(false);
+
+            if !still_running {
+                debug!("scheduler: countdown {} in room {} stopped early", countdown_id, room_id);
+                return;
+            }
+        }
+
+        sleep(Duration::from_secs(1)).await;
+
+        let result = self
+            .repository
+            .update_with(room_id, |room| {
+                if !room.is_current_countdown(countdown_id) {
+                    return Ok(None);
+                }
+
+                let revealed_by = room.countdown_started_by;
+                room.complete_countdown();
+                room.touch();
+                RoomChange::publish(room, RoomDelta::cards_revealed);
+                RoomEvent::publish(room, |room| RoomEventPayload::cards_revealed(room, revealed_by));
+                Ok(Some(room.clone()))
+            })
+            .await;
+
+        match result {
+            Ok(Some(room)) => self.on_revealed(&room),
+            Ok(None) => {}
+            Err(err) => {
+                warn!("scheduler: could not complete countdown in room {}: {}", room_id, err.message);
+            }
+        }
+    }
+
+    async fn run_auto_reset(&self, room_id: EntityId, game_id: EntityId) {
+        let result = self
+            .repository
+            .update_with(room_id, |room| {
+                // Someone already started a new round by hand, or turned the reset off.
+                if !room.is_game_over || room.game.id != game_id || room.auto_reset_seconds.is_none() {
+                    return Ok(());
+                }
+
+                room.reset_game(None)?;
+                room.touch();
+                RoomChange::publish(room, RoomDelta::replaced);
+                RoomEvent::publish(room, |room| RoomEventPayload::game_reset(room, None));
+                Ok(())
+            })
+            .await;
+
+        if let Err(err) = result {
+            warn!("scheduler: could not auto-reset room {}: {}", room_id, err.message);
+        }
+    }
+
+    fn forget(&self, room_id: EntityId) {
+        let mut timers = self.timers.lock().unwrap();
+
+        // Only drop our own handle; a newer countdown may already have replaced it.
+        if timers
+            .get(&room_id)
+            .is_some_and(|timer| Some(timer.handle.id()) == tokio::task::try_id())
+        {
+            timers.remove(&room_id);
+            debug!("scheduler: timer for room {} finished", room_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::room::RevealStage, persistence::MemoryStore, repository::InMemoryRoomRepository};
+
+    fn setup(configure: impl FnOnce(&mut Room)) -> (Repository, Arc<RoomScheduler>, EntityId) {
+        let mut room = Room::new(None, vec!["1".to_string(), "2".to_string()]);
+        room.countdown_enabled = true;
+        room.countdown_seconds = 3;
+        configure(&mut room);
+        let room_id = room.id;
+
+        let repository: Repository =
+            Arc::new(InMemoryRoomRepository::with_rooms(Arc::new(MemoryStore), vec![room]));
+        let scheduler = RoomScheduler::new(repository.clone());
+        (repository, scheduler, room_id)
+    }
+
+    /// Start a countdown on the room, as the mutations do, and hand it to the scheduler.
+    async fn start_countdown(repository: &Repository, scheduler: &Arc<RoomScheduler>, room_id: EntityId) -> Uuid {
+        let (countdown_id, seconds) = repository
+            .update_with(room_id, |room| Ok((room.start_countdown(None)?, room.countdown_seconds)))
+            .await
+            .unwrap();
+        scheduler.schedule_countdown(room_id, countdown_id, seconds);
+        countdown_id
+    }
+
+    async fn room(repository: &Repository, room_id: EntityId) -> Room {
+        repository.get(room_id).await.unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_countdown_ticks_down_and_reveals() {
+        let (repository, scheduler, room_id) = setup(|_| {});
+        start_countdown(&repository, &scheduler, room_id).await;
+
+        sleep(Duration::from_millis(1500)).await;
+        let counting = room(&repository, room_id).await;
+        assert_eq!(counting.countdown_value, Some(2));
+        assert!(!counting.is_game_over);
+
+        sleep(Duration::from_secs(2)).await;
+        let revealed = room(&repository, room_id).await;
+        assert!(revealed.is_game_over);
+        assert_eq!(revealed.reveal_stage, Some(RevealStage::Revealed));
+        assert_eq!(revealed.countdown_value, None);
+        assert!(scheduler.timers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_superseded_countdown_stops_acting() {
+        let (repository, scheduler, room_id) = setup(|_| {});
+        start_countdown(&repository, &scheduler, room_id).await;
+
+        // Cancelled and restarted by hand, without telling the scheduler.
+        repository
+            .update_with(room_id, |room| {
+                room.cancel_countdown(None)?;
+                room.start_countdown(None)?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        sleep(Duration::from_secs(5)).await;
+        let room = room(&repository, room_id).await;
+        assert!(!room.is_game_over);
+        assert_eq!(room.countdown_value, Some(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancel_only_stops_the_timer_it_names() {
+        let (repository, scheduler, room_id) = setup(|_| {});
+        start_countdown(&repository, &scheduler, room_id).await;
+
+        scheduler.cancel(room_id, Uuid::new_v4());
+        sleep(Duration::from_millis(1500)).await;
+        assert_eq!(room(&repository, room_id).await.countdown_value, Some(2));
+
+        let countdown_id = room(&repository, room_id).await.countdown_id.unwrap();
+        scheduler.cancel(room_id, countdown_id);
+        assert!(scheduler.timers.lock().unwrap().is_empty());
+
+        sleep(Duration::from_secs(5)).await;
+        let room = room(&repository, room_id).await;
+        assert!(!room.is_game_over);
+        assert_eq!(room.countdown_value, Some(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_revealing_countdown_hands_over_to_the_auto_reset() {
+        let (repository, scheduler, room_id) = setup(|room| {
+            room.countdown_seconds = 1;
+            room.auto_reset_seconds = Some(5);
+        });
+        start_countdown(&repository, &scheduler, room_id).await;
+
+        sleep(Duration::from_millis(1500)).await;
+        let revealed = room(&repository, room_id).await;
+        assert!(revealed.is_game_over);
+        assert_eq!(RoomScheduler::pending_timer_id(&revealed), Some(revealed.game.id));
+
+        sleep(Duration::from_secs(5)).await;
+        let reset = room(&repository, room_id).await;
+        assert!(!reset.is_game_over);
+        assert_ne!(reset.game.id, revealed.game.id);
+        assert!(scheduler.timers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_pending_auto_reset_checks_the_setting_is_still_on() {
+        let (repository, scheduler, room_id) = setup(|room| {
+            room.countdown_seconds = 1;
+            room.auto_reset_seconds = Some(5);
+        });
+        start_countdown(&repository, &scheduler, room_id).await;
+        sleep(Duration::from_millis(1500)).await;
+
+        repository
+            .update_with(room_id, |room| {
+                room.auto_reset_seconds = None;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        sleep(Duration::from_secs(5)).await;
+        assert!(room(&repository, room_id).await.is_game_over);
+    }
+}
@@ -1,31 +1,108 @@
-use std::collections::HashMap;
-use tokio::time::{sleep, Duration};
+use std::sync::Arc;
 
 use crate::{
+    auth::{admin_required, is_admin, require_admin, require_user, AuthenticatedUser, SessionSigner},
     domain::{
-        game::{Game, UserCard},
-        room::Room,
+        error::RoomError,
+        deck::{self, DeckCardInput, DeckPreset, DeckPresetInfo},
+        passcode,
+        room::{Room, RoomSummary, VoteLockPolicy},
+        room_change::{RoomChange, RoomDelta},
+        room_event::{
+            DeckChangedPayload, FinalEstimateSetPayload, OwnerChangedPayload, RoomEvent,
+            RoomEventPayload, RoomRenamedPayload, UserBannedPayload, UserKickedPayload,
+        },
+        story::{self, Story, StoryStatus, MAX_STORY_DESCRIPTION_LENGTH, MAX_STORY_KEY_LENGTH},
+        story_import::{parse_stories, CsvColumnMapping, StoryImportError, StoryImportFormat},
         user::{User, UserInput},
         chat::{ChatMessage, ChatPosition, ChatPositionInput}
     },
+    presence::{PresenceTracker, Tracked},
+    scheduler::RoomScheduler,
     simple_broker::SimpleBroker,
-    types::{Card, EntityId, Storage},
+    types::{Card, EntityId, Repository},
 };
 use async_graphql::*;
 use futures_util::{Stream, StreamExt};
-use tokio::sync::MutexGuard;
 use uuid::Uuid;
 
-#[derive(Clone, Debug, SimpleObject)]
-pub struct RoomEvent {
-    pub room_id: Uuid,
-    pub event_type: String,
-    pub target_user_id: Option<Uuid>,
-    pub room: Room,
+fn get_repository<'a>(ctx: &'a Context<'_>) -> &'a Repository {
+    ctx.data_unchecked::<Repository>()
+}
+
+fn get_scheduler<'a>(ctx: &'a Context<'_>) -> &'a Arc<RoomScheduler> {
+    ctx.data_unchecked::<Arc<RoomScheduler>>()
+}
+
+fn get_presence<'a>(ctx: &'a Context<'_>) -> &'a Arc<PresenceTracker> {
+    ctx.data_unchecked::<Arc<PresenceTracker>>()
+}
+
+/// Keep the subscribing user (if the connection is authenticated) online in
+/// the room for as long as `stream` lives.
+fn track_presence<S: Stream + Unpin>(ctx: &Context<'_>, room_id: EntityId, stream: S) -> Tracked<S> {
+    let guard = ctx
+        .data_opt::<AuthenticatedUser>()
+        .map(|user| get_presence(ctx).connect(room_id, user.0));
+
+    Tracked::new(stream, guard)
+}
+
+/// End `stream` at the first item that reaches the subscriber after they left
+/// the room (kicked, banned or gone), unless `about_subscriber` says the item
+/// concerns them. Admins watching without a user are never cut off.
+fn while_member<S: Stream>(
+    ctx: &Context<'_>,
+    room_id: EntityId,
+    stream: S,
+    about_subscriber: fn(&S::Item, EntityId) -> bool,
+) -> impl Stream<Item = S::Item> {
+    let repository = get_repository(ctx).clone();
+    let user_id = ctx
+        .data_opt::<AuthenticatedUser>()
+        .map(|user| user.0)
+        .filter(|_| !is_admin(ctx));
+
+    stream.take_while(move |item| {
+        let check = user_id
+            .filter(|&user_id| !about_subscriber(item, user_id))
+            .map(|user_id| (repository.clone(), user_id));
+        async move {
+            match check {
+                Some((repository, user_id)) => repository.is_member(room_id, user_id).await,
+                None => true,
+            }
+        }
+    })
+}
+
+/// The room, if the caller is one of its members (or an admin); otherwise the
+/// reason why not, unlike `roomById` which answers `null` either way.
+async fn require_member(ctx: &Context<'_>, room_id: EntityId) -> Result<Room> {
+    let user_id = if is_admin(ctx) { None } else { Some(require_user(ctx)?) };
+    let room = get_repository(ctx)
+        .get(room_id)
+        .await
+        .ok_or(RoomError::RoomNotFound)?;
+
+    if user_id.is_some_and(|user_id| !room.is_user_exist(user_id)) {
+        return Err(RoomError::NotRoomMember.into());
+    }
+    Ok(room)
 }
 
-async fn get_storage<'a>(ctx: &'a Context<'_>) -> MutexGuard<'a, HashMap<Uuid, Room>> {
-    ctx.data_unchecked::<Storage>().lock().await
+/// Hash a passcode off the async runtime; an empty or missing passcode means none.
+async fn hash_passcode(passcode: Option<String>) -> Result<Option<String>> {
+    let Some(passcode) = passcode.filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+
+    let hash = tokio::task::spawn_blocking(move || passcode::hash_passcode(&passcode)).await??;
+    Ok(Some(hash))
+}
+
+async fn verify_passcode(hash: String, passcode: String) -> Result<bool> {
+    Ok(tokio::task::spawn_blocking(move || passcode::verify_passcode(&hash, &passcode)).await?)
 }
 
 pub type PokerPlanningSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
@@ -34,80 +111,213 @@ pub struct QueryRoot;
 
 #[Object]
 impl QueryRoot {
-    async fn rooms(&self, ctx: &Context<'_>) -> Result<Vec<Room>> {
-        let storage = get_storage(ctx).await;
-        Ok(storage.clone().into_values().collect())
+    /// The built-in decks `createRoom` and `updateDeck` accept as `preset`.
+    async fn decks(&self) -> Vec<DeckPresetInfo> {
+        DeckPreset::ALL.into_iter().map(DeckPresetInfo::from).collect()
     }
 
-    async fn user_rooms(&self, ctx: &Context<'_>, user_id: EntityId) -> Result<Vec<Room>> {
-        let storage = get_storage(ctx).await;
+    /// Every room on the instance. Admin only.
+    async fn rooms(&self, ctx: &Context<'_>) -> Result<Vec<RoomSummary>> {
+        require_admin(ctx)?;
 
-        let rooms = storage
-            .clone()
-            .into_iter()
-            .fold(vec![], |mut acc, (_, room)| {
-                if room
-                    .users
-                    .iter()
-                    .any(|user_in_room| user_in_room.id == user_id)
-                {
-                    acc.push(room);
-                }
+        let mut rooms = Vec::new();
+        get_repository(ctx)
+            .for_each(&mut |room| rooms.push(RoomSummary::from(room)))
+            .await;
+        Ok(rooms)
+    }
+
+    /// The rooms a user is in. Callers may only list their own, unless they are admins.
+    async fn user_rooms(&self, ctx: &Context<'_>, user_id: EntityId) -> Result<Vec<RoomSummary>> {
+        if !is_admin(ctx) && require_user(ctx)? != user_id {
+            return Err(admin_required());
+        }
 
-                acc
-            });
+        let rooms = get_repository(ctx).find_by_user(user_id).await;
+        Ok(rooms.iter().map(RoomSummary::from).collect())
+    }
 
-        Ok(rooms)
+    /// What someone may learn of a room before joining it: whether it exists,
+    /// its name and whether it takes a passcode. Needs no session.
+    async fn room_preview(&self, ctx: &Context<'_>, room_id: EntityId) -> RoomPreview {
+        match get_repository(ctx).get(room_id).await {
+            Some(room) => RoomPreview {
+                exists: true,
+                name: room.name,
+                has_passcode: room.passcode_hash.is_some(),
+            },
+            None => RoomPreview { exists: false, name: None, has_passcode: false },
+        }
     }
 
+    /// A room the caller belongs to; `null` if it doesn't exist or they aren't a
+    /// member, so use `roomPreview` to tell those apart.
     async fn room_by_id(&self, ctx: &Context<'_>, room_id: Uuid) -> Result<Option<Room>> {
-        let storage = get_storage(ctx).await;
-        Ok(storage.get(&room_id).cloned())
+        let room = get_repository(ctx).get(room_id).await;
+
+        if is_admin(ctx) {
+            return Ok(room.map(|room| room.get_room()));
+        }
+
+        let user_id = require_user(ctx)?;
+        Ok(room
+            .filter(|room| room.is_user_exist(user_id))
+            .map(|room| room.get_room()))
+    }
+
+    /// Resync after a gap in `roomChanges`: the room's current version, and the
+    /// room itself unless it is still at `sinceVersion`. Fails with
+    /// `ROOM_NOT_FOUND` or `NOT_ROOM_MEMBER`.
+    async fn room_sync(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        since_version: u64,
+    ) -> Result<RoomSync> {
+        let room = require_member(ctx, room_id).await?;
+
+        // Any other version counts as moved on: changes that aren't persisted
+        // can leave a restarted server behind what the client last saw.
+        Ok(RoomSync {
+            version: room.version,
+            room: (room.version != since_version).then(|| room.get_room()),
+        })
     }
 }
 
+/// A room as seen from outside it.
+#[derive(SimpleObject)]
+pub struct RoomPreview {
+    pub exists: bool,
+    pub name: Option<String>,
+    pub has_passcode: bool,
+}
+
+/// A room's version, with the room when the caller's copy is out of date.
+#[derive(SimpleObject)]
+pub struct RoomSync {
+    pub version: u64,
+    /// `null` when the room hasn't changed since `sinceVersion`.
+    pub room: Option<Room>,
+}
+
 #[derive(InputObject)]
 pub struct UpdateDeckInput {
     pub room_id: Uuid,
-    pub cards: Vec<String>
+    /// Give at most one of `cards` (plain labels), `cardDetails` or `preset`;
+    /// neither, or no `cards`, means the default deck.
+    pub cards: Option<Vec<String>>,
+    pub card_details: Option<Vec<DeckCardInput>>,
+    pub preset: Option<DeckPreset>,
+}
+
+#[derive(InputObject)]
+pub struct RoomSettingsInput {
+    pub room_id: Uuid,
+    /// Reveal countdown length in seconds (3-10).
+    pub countdown_seconds: Option<i32>,
+    /// Reveal automatically once everyone has voted.
+    pub auto_reveal: Option<bool>,
+    /// Seconds after a reveal before a new round starts; `null` turns it off.
+    pub auto_reset_seconds: MaybeUndefined<i32>,
+    /// When votes stop being accepted.
+    pub vote_lock: Option<VoteLockPolicy>,
+}
+
+#[derive(InputObject)]
+pub struct AddStoryInput {
+    pub room_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub external_key: Option<String>,
+}
+
+/// Fields left out are unchanged; `null` clears an optional field.
+#[derive(InputObject)]
+pub struct UpdateStoryInput {
+    pub room_id: Uuid,
+    pub story_id: Uuid,
+    pub title: Option<String>,
+    pub description: MaybeUndefined<String>,
+    pub external_key: MaybeUndefined<String>,
+    /// `VOTING` selects the story; anything else deselects it if it was selected.
+    pub status: Option<StoryStatus>,
+}
+
+#[derive(InputObject)]
+pub struct ImportStoriesInput {
+    pub room_id: Uuid,
+    pub format: StoryImportFormat,
+    /// The CSV or JSON document itself.
+    pub data: String,
+    /// CSV only; defaults to the `title`, `description` and `key` columns.
+    pub columns: Option<CsvColumnMapping>,
+}
+
+/// Outcome of a backlog import: rejected rows don't stop the others.
+#[derive(SimpleObject)]
+pub struct StoryImportResult {
+    pub room: Room,
+    pub imported_count: usize,
+    pub errors: Vec<StoryImportError>,
 }
 
 #[derive(InputObject)]
 pub struct SendChatInput {
     pub room_id: Uuid,
-    pub user_id: Uuid,
-    pub username: String,
     pub content: String,
     pub formatted_content: Option<String>,
     pub content_type: String,
     pub position: Option<ChatPositionInput>
 }
 
+/// A freshly created user and the token to send as `Authorization: Bearer <token>`
+/// (or as `authToken` in the WebSocket `connection_init` payload).
+#[derive(SimpleObject)]
+pub struct UserSession {
+    pub user: User,
+    pub token: String,
+}
+
 pub struct MutationRoot;
 
 #[Object]
 impl MutationRoot {
+    /// Create a room. Clients may pick its `roomId`, but never that of a room
+    /// that already exists. Without `cards`, `cardDetails` or a `preset` (or with
+    /// no `cards`) it gets the default deck.
+    #[allow(clippy::too_many_arguments)]
     async fn create_room(
         &self,
         ctx: &Context<'_>,
         room_id: Option<Uuid>,
         name: Option<String>,
-        cards: Vec<Card>,
+        cards: Option<Vec<Card>>,
+        card_details: Option<Vec<DeckCardInput>>,
+        preset: Option<DeckPreset>,
+        passcode: Option<String>,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
-        let mut room = Room::new_with_id(room_id, name, cards);
+        let (cards, preset) = deck::resolve_cards(cards, card_details, preset)?;
+        let mut room = Room::new_with_id(room_id, name, vec![]);
+        // Nobody owns the room yet, so it is set up directly.
+        room.deck.set_cards(cards, preset);
+        room.passcode_hash = hash_passcode(passcode).await?;
 
         room.touch();
 
         // Store and publish
-        storage.insert(room.id, room.clone());
-        SimpleBroker::publish(room.get_room());
+        get_repository(ctx).insert(room.clone()).await?;
+        RoomChange::publish(&room, RoomDelta::replaced);
 
         Ok(room.get_room())
     }
 
-    async fn create_user(&self, username: String) -> User {
-        User::new(username)
+    /// Create a user and the session token that identifies them on later requests.
+    async fn create_user(&self, ctx: &Context<'_>, username: String) -> UserSession {
+        let user = User::new(username);
+        let token = ctx.data_unchecked::<SessionSigner>().issue(user.id);
+
+        UserSession { user, token }
     }
 
     async fn join_room(
@@ -116,54 +326,94 @@ impl MutationRoot {
         room_id: EntityId,
         user: UserInput,
         room_owner_id: Option<EntityId>,
+        passcode: Option<String>,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
-
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                if room.is_banned(user.id) {
-                    return Err(Error::new("User is banned from this room"));
+        let user_id = require_user(ctx)?;
+        let repository = get_repository(ctx);
+        let presence = get_presence(ctx);
+
+        // Check the passcode before taking the room lock; hashing is slow on purpose.
+        let snapshot = repository.get(room_id).await.ok_or(RoomError::RoomNotFound)?;
+        let checked_hash = match snapshot.passcode_hash.clone() {
+            Some(hash) if snapshot.requires_passcode(user_id) && !snapshot.is_banned(user_id) => {
+                let passcode = passcode.ok_or(RoomError::PasscodeRequired)?;
+                if !verify_passcode(hash.clone(), passcode).await? {
+                    return Err(RoomError::WrongPasscode.into());
                 }
+                Some(hash)
+            }
+            _ => None,
+        };
 
-                let is_new_user = !room.users.iter().any(|u| u.id == user.id);
+        repository
+            .update_with(room_id, |room| {
+                if room.is_banned(user_id) {
+                    return Err(RoomError::Banned.into());
+                }
 
-                if is_new_user {
-                    if let Some(name) = &user.room_name {
-                        room.name = Some(name.clone());
-                    }
+                // The passcode changed while we were checking the old one.
+                if room.requires_passcode(user_id) && room.passcode_hash != checked_hash {
+                    return Err(RoomError::WrongPasscode.into());
+                }
 
-                    room.users.push(user.clone().into());
+                let is_new_user = !room.users.iter().any(|u| u.id == user_id);
 
-                    if let Some(owner_id) = room_owner_id {
-                        let _ = room.set_room_owner(Some(owner_id));
+                if is_new_user {
+                    let mut new_user = User::from_input(user_id, user.clone());
+                    new_user.last_card_value = new_user
+                        .last_card_picked
+                        .as_deref()
+                        .and_then(|card| room.deck.value_of(card))
+                        .map(|value| value as f32);
+                    room.users.push(new_user);
+                    // Clients may subscribe to the room before joining it.
+                    room.set_online(user_id, presence.is_connected(room_id, user_id));
+
+                    // Whoever creates the room claims it on their first join; an
+                    // existing owner can't be displaced this way.
+                    let claims_room = room.room_owner_id.is_none() && room_owner_id == Some(user_id);
+                    if claims_room {
+                        room.set_room_owner(Some(user_id))?;
+                        // Only the creator names the room on the way in.
+                        if let Some(name) = &user.room_name {
+                            room.rename(user_id, Some(name.clone()))?;
+                        }
                     }
 
                     room.touch();
 
-                    SimpleBroker::publish(room.get_room());
+                    if claims_room {
+                        RoomChange::publish(room, RoomDelta::replaced);
+                    } else {
+                        RoomChange::publish(room, |room| RoomDelta::user_joined(room, user_id));
+                    }
                 }
 
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
     async fn update_deck(&self, ctx: &Context<'_>, input: UpdateDeckInput) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
+        let (cards, preset) = deck::resolve_cards(input.cards, input.card_details, input.preset)?;
 
-        match storage.get_mut(&input.room_id) {
-            Some(room) => {
-                room.deck.cards = input.cards.clone();
+        get_repository(ctx)
+            .update_with(input.room_id, |room| {
+                room.set_deck(user_id, cards, preset)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::deck_updated);
+                RoomEvent::publish(room, |room| {
+                    RoomEventPayload::DeckChanged(DeckChangedPayload {
+                        deck: room.deck.clone(),
+                    })
+                });
 
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
     async fn rename_room(
@@ -172,128 +422,252 @@ impl MutationRoot {
         room_id: EntityId,
         name: Option<String>,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.rename(name);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.rename(user_id, name)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::settings_changed);
+                RoomEvent::publish(room, |room| {
+                    RoomEventPayload::RoomRenamed(RoomRenamedPayload {
+                        name: room.name.clone(),
+                    })
+                });
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
-    async fn toggle_countdown_option(
+    /// Name the story being estimated; it is recorded with the round when revealed.
+    async fn set_round_title(
         &self,
         ctx: &Context<'_>,
-        room_id: Uuid,
-        enabled: bool
+        room_id: EntityId,
+        title: Option<String>,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.enable_countdown(enabled);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.set_round_title(user_id, title)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::replaced);
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
-    async fn start_reveal_countdown(
+    async fn add_story(&self, ctx: &Context<'_>, input: AddStoryInput) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+        let story = Story::new(input.title, input.description, input.external_key)?;
+
+        get_repository(ctx)
+            .update_with(input.room_id, |room| {
+                room.add_story(user_id, story)?;
+
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::replaced);
+                Ok(room.get_room())
+            })
+            .await
+    }
+
+    /// Append stories from a CSV or JSON export to the backlog.
+    async fn import_stories(
         &self,
         ctx: &Context<'_>,
-        room_id: Uuid,
-        user_id: Option<Uuid>, // optional so only owner can start
+        input: ImportStoriesInput,
+    ) -> Result<StoryImportResult> {
+        let user_id = require_user(ctx)?;
+        let columns = input.columns.unwrap_or_default();
+        let parsed = parse_stories(input.format, &input.data, &columns)?;
+
+        get_repository(ctx)
+            .update_with(input.room_id, |room| {
+                let (imported_count, rejected) = room.import_stories(user_id, parsed.stories)?;
+                let mut errors = parsed.errors;
+                errors.extend(rejected);
+                errors.sort_by_key(|error| error.row);
+
+                if imported_count > 0 {
+                    room.touch();
+                    RoomChange::publish(room, RoomDelta::replaced);
+                }
+
+                Ok(StoryImportResult {
+                    room: room.get_room(),
+                    imported_count,
+                    errors,
+                })
+            })
+            .await
+    }
+
+    async fn update_story(&self, ctx: &Context<'_>, input: UpdateStoryInput) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+
+        let title = input.title.map(story::validate_title).transpose()?;
+        let description =
+            story::validate_update(input.description, "Description", MAX_STORY_DESCRIPTION_LENGTH)?;
+        let external_key =
+            story::validate_update(input.external_key, "External key", MAX_STORY_KEY_LENGTH)?;
+
+        get_repository(ctx)
+            .update_with(input.room_id, |room| {
+                room.update_story(
+                    user_id,
+                    input.story_id,
+                    title,
+                    description,
+                    external_key,
+                    input.status,
+                )?;
+
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::replaced);
+                Ok(room.get_room())
+            })
+            .await
+    }
+
+    async fn delete_story(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        story_id: EntityId,
     ) -> Result<Room> {
-        {
-            let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-            let room = storage.get_mut(&room_id).ok_or(Error::new("Room not found"))?;
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.delete_story(user_id, story_id)?;
 
-            if let Some(uid) = user_id {
-                if Some(uid) != room.room_owner_id {
-                    return Err(Error::new("Only the room owner can start the countdown"));
-                }
-            }
+                room.touch();
 
-            if !room.countdown_enabled {
-                return Err(Error::new("Countdown reveal is disabled for this room"));
-            }
+                RoomChange::publish(room, RoomDelta::replaced);
+                Ok(room.get_room())
+            })
+            .await
+    }
 
-            room.start_countdown();
-            room.touch();
+    /// Reorder the backlog; `story_ids` must list every story in the room once.
+    async fn reorder_stories(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        story_ids: Vec<EntityId>,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
 
-            SimpleBroker::publish(room.get_room());
-        }
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.reorder_stories(user_id, &story_ids)?;
 
-        for remaining in (1..=3).rev() {
-            sleep(Duration::from_secs(1)).await;
+                room.touch();
 
-            let mut storage = get_storage(ctx).await;
-            if let Some(room) = storage.get_mut(&room_id) {
-                // If countdown cancelled, stop
-                if room.reveal_stage.as_deref() == Some("cancelled") {
-                    room.countdown_value = None;
-                    SimpleBroker::publish(room.get_room());
-                    return Ok(room.get_room());
-                }
+                RoomChange::publish(room, RoomDelta::replaced);
+                Ok(room.get_room())
+            })
+            .await
+    }
+
+    /// Choose the story to vote on; `null` clears the selection.
+    async fn select_story(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        story_id: Option<EntityId>,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.select_story(user_id, story_id)?;
 
-                room.update_countdown_value(remaining);
                 room.touch();
-                SimpleBroker::publish(room.get_room());
-            }
-            drop(storage);
-        }
 
-        let mut storage = get_storage(ctx).await;
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                if room.reveal_stage.as_deref() != Some("cancelled") {
-                    room.complete_countdown();
-                    room.touch();
-                    SimpleBroker::publish(room.get_room());
-                }
+                RoomChange::publish(room, RoomDelta::replaced);
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
-    async fn cancel_reveal_countdown(
+    async fn toggle_countdown_option(
         &self,
         ctx: &Context<'_>,
         room_id: Uuid,
-        user_id: Option<Uuid>,
+        enabled: bool
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
-
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                // Ownership check
-                if let Some(uid) = user_id {
-                    if Some(uid) != room.room_owner_id {
-                        return Err(Error::new("Only the room owner can cancel the countdown"));
-                    }
-                }
+        let user_id = require_user(ctx)?;
 
-                room.cancel_countdown();
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.enable_countdown(user_id, enabled)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::settings_changed);
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
+            })
+            .await
+    }
+
+    async fn start_reveal_countdown(
+        &self,
+        ctx: &Context<'_>,
+        room_id: Uuid,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+
+        let (room, countdown_id, seconds) = get_repository(ctx)
+            .update_with(room_id, |room| {
+                let countdown_id = room.start_countdown(Some(user_id))?;
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::reveal_stage_changed);
+                Ok((room.get_room(), countdown_id, room.countdown_value.unwrap_or(0)))
+            })
+            .await?;
+
+        // The countdown runs on the server; the caller gets the room back right away.
+        get_scheduler(ctx)
+            .schedule_countdown(room_id, countdown_id, seconds);
+
+        Ok(room)
+    }
+
+    async fn cancel_reveal_countdown(
+        &self,
+        ctx: &Context<'_>,
+        room_id: Uuid,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+
+        let (room, countdown_id) = get_repository(ctx)
+            .update_with(room_id, |room| {
+                let countdown_id = room.countdown_id.filter(|_| room.is_countdown_running());
+                room.cancel_countdown(Some(user_id))?;
+
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::reveal_stage_changed);
+                Ok((room.get_room(), countdown_id))
+            })
+            .await?;
+
+        // Only this countdown: another may have started since the lock was released.
+        if let Some(countdown_id) = countdown_id {
+            get_scheduler(ctx).cancel(room_id, countdown_id);
         }
+
+        Ok(room)
     }
 
     async fn set_room_owner(
@@ -302,82 +676,80 @@ impl MutationRoot {
         room_id: Uuid,
         user_id: Option<Uuid>
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let caller_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.set_room_owner(user_id)?;
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                let previous_owner_id = room.room_owner_id;
+                room.transfer_ownership(caller_id, user_id)?;
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::replaced);
+                RoomEvent::publish(room, |room| {
+                    RoomEventPayload::OwnerChanged(OwnerChangedPayload {
+                        previous_owner_id,
+                        owner_id: room.room_owner_id,
+                    })
+                });
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
     async fn edit_user(
         &self,
         ctx: &Context<'_>,
-        user_id: EntityId,
         username: String,
     ) -> Result<User> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
+        let repository = get_repository(ctx);
+        let mut existing = None;
+
+        for room in repository.find_by_user(user_id).await {
+            let updated = repository
+                .update_with(room.id, |room| {
+                    if !room.is_user_exist(user_id) {
+                        return Ok(None);
+                    }
 
-        *storage = storage
-            .clone()
-            .into_iter()
-            .map(|(key, mut room)| {
-                if room.is_user_exist(user_id) {
                     room.edit_user(user_id, username.clone());
 
                     room.touch();
 
-                    SimpleBroker::publish(room.get_room());
-                }
-                (key, room)
-            })
-            .collect();
+                    RoomChange::publish(room, |room| RoomDelta::user_updated(room, user_id));
 
-        let (last_card_picked, last_card_value, last_seen_chat_message_id) = storage
-            .values()
-            .find_map(|room| {
-                room.users
-                    .iter()
-                    .find(|u| u.id == user_id)
-                    .map(|u| (
-                        u.last_card_picked.clone(),
-                        u.last_card_value,
-                        u.last_seen_chat_message_id
-                    ))
-            })
-            .unwrap_or((None, None, None));
+                    Ok(room.users.iter().find(|u| u.id == user_id).cloned())
+                })
+                .await;
 
-        Ok(User {
+            if existing.is_none() {
+                existing = updated.ok().flatten();
+            }
+        }
+
+        // Outside any room there is nothing else to report than the new name.
+        Ok(existing.unwrap_or_else(|| User {
             id: user_id,
-            username,
-            last_card_picked,
-            last_card_value,
-            last_seen_chat_message_id
-        })
+            ..User::new(username)
+        }))
     }
 
-    async fn logout(&self, ctx: &Context<'_>, user_id: Option<EntityId>) -> Result<bool> {
-        if let Some(uid) = user_id {
-            let mut storage = get_storage(ctx).await;
+    async fn logout(&self, ctx: &Context<'_>) -> Result<bool> {
+        let uid = require_user(ctx)?;
+        let repository = get_repository(ctx);
 
-            *storage = storage
-                .clone()
-                .into_iter()
-                .map(|(key, mut room)| {
+        for room in repository.find_by_user(uid).await {
+            // The room may have expired since the lookup; nothing to leave then.
+            let _ = repository
+                .update_with(room.id, |room| {
                     if room.is_user_exist(uid) {
                         room.remove_user(uid);
                         room.touch();
-                        SimpleBroker::publish(room.get_room());
+                        RoomChange::publish(room, |_| RoomDelta::user_left(uid, false));
                     }
-                    (key, room)
+                    Ok(())
                 })
-                .collect();
+                .await;
         }
 
         Ok(true)
@@ -386,77 +758,169 @@ impl MutationRoot {
     async fn pick_card(
         &self,
         ctx: &Context<'_>,
-        user_id: EntityId,
         room_id: EntityId,
         card: String,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
+
+        let (room, auto_countdown, auto_revealed) = get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.cast_vote(user_id, &card)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.game.table.retain(|u| u.user_id != user_id);
+                let mut auto_countdown = None;
+                let mut auto_revealed = false;
 
-                if let Some(user) = room.users.iter_mut().find(|u| u.id == user_id) {
-                    if card.trim().is_empty() {
-                        user.last_card_picked = None;
-                        user.last_card_value = None;
+                if room.should_auto_reveal() {
+                    if room.countdown_enabled {
+                        auto_countdown = Some(room.start_countdown(None)?);
                     } else {
-                        user.last_card_picked = Some(card.clone());
-                        user.last_card_value = crate::domain::user::parse_card_to_number(&card);
+                        room.reveal(None)?;
+                        auto_revealed = true;
                     }
                 }
 
-                if !card.trim().is_empty() {
-                    room.game.table.push(UserCard::new(user_id, card));
-                }
-
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
-                Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
+                if auto_revealed {
+                    RoomChange::publish(room, RoomDelta::cards_revealed);
+                    RoomEvent::publish(room, |room| RoomEventPayload::cards_revealed(room, None));
+                } else if auto_countdown.is_some() {
+                    RoomChange::publish(room, RoomDelta::replaced);
+                } else {
+                    RoomChange::publish(room, |room| RoomDelta::vote_cast(room, user_id));
+                }
+                Ok((room.clone(), auto_countdown, auto_revealed))
+            })
+            .await?;
+
+        // A vote changed after the reveal must not restart the auto-reset timer.
+        let scheduler = get_scheduler(ctx);
+        if let Some(countdown_id) = auto_countdown {
+            scheduler.schedule_countdown(room_id, countdown_id, room.countdown_seconds);
+        } else if auto_revealed {
+            scheduler.on_revealed(&room);
         }
+
+        Ok(room.get_room())
     }
 
     async fn show_cards(&self, ctx: &Context<'_>, room_id: EntityId) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.is_game_over = true;
+        let room = get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.reveal(Some(user_id))?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::cards_revealed);
+                RoomEvent::publish(room, |room| RoomEventPayload::cards_revealed(room, Some(user_id)));
 
-                Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+                Ok(room.clone())
+            })
+            .await?;
+
+        get_scheduler(ctx).on_revealed(&room);
+
+        Ok(room.get_room())
     }
 
     async fn reset_game(&self, ctx: &Context<'_>, room_id: EntityId) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
+
+        let (room, timer_id) = get_repository(ctx)
+            .update_with(room_id, |room| {
+                let timer_id = RoomScheduler::pending_timer_id(room);
+                room.reset_game(Some(user_id))?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.is_game_over = false;
-                room.game = Game::new();
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::replaced);
+                RoomEvent::publish(room, |room| RoomEventPayload::game_reset(room, Some(user_id)));
+
+                Ok((room.get_room(), timer_id))
+            })
+            .await?;
+
+        // Drops a pending auto-reset (or a countdown that was still running),
+        // but not a timer started after this reset.
+        if let Some(timer_id) = timer_id {
+            get_scheduler(ctx).cancel(room_id, timer_id);
+        }
+
+        Ok(room)
+    }
 
-                for u in room.users.iter_mut() {
-                    u.last_card_picked = None;
-                    u.last_card_value = None;
+    async fn update_room_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: RoomSettingsInput,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+
+        let (room, auto_reset_changed) = get_repository(ctx)
+            .update_with(input.room_id, |room| {
+                let auto_reset_before = room.auto_reset_seconds;
+
+                if let Some(seconds) = input.countdown_seconds {
+                    room.set_countdown_seconds(user_id, seconds)?;
                 }
 
-                room.touch();
+                if let Some(enabled) = input.auto_reveal {
+                    room.set_auto_reveal(user_id, enabled)?;
+                }
 
-                SimpleBroker::publish(room.get_room());
+                if let Some(policy) = input.vote_lock {
+                    room.set_vote_lock(user_id, policy)?;
+                }
 
-                Ok(room.get_room())
+                match input.auto_reset_seconds {
+                    MaybeUndefined::Value(seconds) => room.set_auto_reset_seconds(user_id, Some(seconds))?,
+                    MaybeUndefined::Null => room.set_auto_reset_seconds(user_id, None)?,
+                    MaybeUndefined::Undefined => {}
+                }
+
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::settings_changed);
+                Ok((room.clone(), room.auto_reset_seconds != auto_reset_before))
+            })
+            .await?;
+
+        // A revealed round waits on the new setting, or on nothing.
+        if auto_reset_changed && room.is_game_over {
+            let scheduler = get_scheduler(ctx);
+            match room.auto_reset_seconds {
+                Some(_) => scheduler.on_revealed(&room),
+                None => scheduler.cancel(room.id, room.game.id),
             }
-            None => Err(Error::new("Room not found")),
         }
+
+        Ok(room.get_room())
+    }
+
+    /// Switch a user between spectating and voting. Anyone can switch
+    /// themselves; switching someone else takes the owner.
+    async fn set_spectator(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        target_user_id: Option<EntityId>,
+        spectator: bool,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+        let target_user_id = target_user_id.unwrap_or(user_id);
+
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.set_spectator(user_id, target_user_id, spectator)?;
+
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::replaced);
+                Ok(room.get_room())
+            })
+            .await
     }
 
     async fn kick_user(
@@ -465,29 +929,48 @@ impl MutationRoot {
         room_id: EntityId,
         target_user_id: EntityId,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.kick_user(target_user_id);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.kick_user(user_id, target_user_id)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, |_| RoomDelta::user_left(target_user_id, false));
+                RoomEvent::publish(room, |_| {
+                    RoomEventPayload::UserKicked(UserKickedPayload {
+                        user_id: target_user_id,
+                        kicked_by: user_id,
+                    })
+                });
 
-                let event = RoomEvent {
-                    room_id,
-                    event_type: "USER_KICKED".to_string(),
-                    target_user_id: Some(target_user_id),
-                    room: room.get_room(),
-                };
+                Ok(room.get_room())
+            })
+            .await
+    }
+
+    /// Set, change or (with `null` or an empty string) remove the room passcode.
+    async fn set_room_passcode(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        passcode: Option<String>,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
+        let passcode_hash = hash_passcode(passcode).await?;
+
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.set_passcode_hash(user_id, passcode_hash)?;
 
-                SimpleBroker::publish(event);
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::settings_changed);
 
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
     async fn ban_user(
@@ -496,29 +979,55 @@ impl MutationRoot {
         room_id: EntityId,
         target_user_id: EntityId,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-            match storage.get_mut(&room_id) {
-                Some(room) => {
-                    room.ban_user(target_user_id);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.ban_user(user_id, target_user_id)?;
 
-                    room.touch();
+                room.touch();
 
-                    SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, |_| RoomDelta::user_left(target_user_id, true));
+                RoomEvent::publish(room, |_| {
+                    RoomEventPayload::UserBanned(UserBannedPayload {
+                        user_id: target_user_id,
+                        banned_by: user_id,
+                    })
+                });
 
-                    let event = RoomEvent {
-                        room_id,
-                        event_type: "USER_BANNED".to_string(),
-                        target_user_id: Some(target_user_id),
-                        room: room.get_room(),
-                    };
+                Ok(room.get_room())
+            })
+            .await
+    }
 
-                    SimpleBroker::publish(event);
+    /// Record the estimate the team agreed on for the revealed round; `null` clears it.
+    async fn set_final_estimate(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+        card: Option<Card>,
+    ) -> Result<Room> {
+        let user_id = require_user(ctx)?;
 
-                    Ok(room.get_room())
-                }
-                None => Err(Error::new("Room not found")),
-            }
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.set_final_estimate(card, user_id)?;
+
+                room.touch();
+
+                RoomChange::publish(room, RoomDelta::replaced);
+                RoomEvent::publish(room, |room| {
+                    let round = room.round_history.last();
+                    RoomEventPayload::FinalEstimateSet(FinalEstimateSetPayload {
+                        card: round.and_then(|round| round.final_estimate.clone()),
+                        story_id: round.and_then(|round| round.story_id),
+                        set_by: user_id,
+                    })
+                });
+
+                Ok(room.get_room())
+            })
+            .await
     }
 
     async fn unban_user(
@@ -527,19 +1036,18 @@ impl MutationRoot {
         room_id: EntityId,
         target_user_id: EntityId,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.unban_user(target_user_id);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.unban_user(user_id, target_user_id)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::replaced);
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
     async fn toggle_confirm_new_game(
@@ -548,100 +1056,125 @@ impl MutationRoot {
         room_id: Uuid,
         enabled: bool,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.toggle_confirm_new_game(enabled);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.toggle_confirm_new_game(user_id, enabled)?;
 
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, RoomDelta::settings_changed);
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 
-     async fn send_chat_message(
+    async fn send_chat_message(
         &self,
         ctx: &Context<'_>,
         input: SendChatInput,
-     ) -> Result<ChatMessage> {
-        let mut storage = get_storage(ctx).await;
-        let room = storage
-            .get_mut(&input.room_id)
-            .ok_or(Error::new("Room not found"))?;
-
-        let msg = ChatMessage::new(
-            input.room_id,
-            input.user_id,
-            input.username.clone(),
-            input.content.clone(),
-            input.formatted_content.clone(),
-            input.content_type.clone(),
-            input.position.clone().map(|p| ChatPosition {
-                x: p.x,
-                y: p.y,
-                width: p.width,
-                height: p.height,
-            }),
-        );
-
-        room.push_chat(msg.clone());
+    ) -> Result<ChatMessage> {
+        let user_id = require_user(ctx)?;
 
-        room.touch();
-        SimpleBroker::publish(msg.clone());
-        SimpleBroker::publish(room.get_room());
+        get_repository(ctx)
+            .update_with(input.room_id, |room| {
+                let username = room
+                    .users
+                    .iter()
+                    .find(|u| u.id == user_id)
+                    .map(|u| u.username.clone())
+                    .unwrap_or_default();
+
+                let msg = ChatMessage::new(
+                    input.room_id,
+                    user_id,
+                    username,
+                    input.content.clone(),
+                    input.formatted_content.clone(),
+                    input.content_type.clone(),
+                    input.position.clone().map(|p| ChatPosition {
+                        x: p.x,
+                        y: p.y,
+                        width: p.width,
+                        height: p.height,
+                    }),
+                );
+
+                room.push_chat(msg.clone())?;
 
-        Ok(msg)
+                room.touch();
+                SimpleBroker::publish(msg.clone());
+                RoomChange::publish(room, |_| RoomDelta::chat_message_sent(msg.clone()));
+                Ok(msg)
+            })
+            .await
     }
 
     async fn mark_chat_seen(
         &self,
         ctx: &Context<'_>,
         room_id: Uuid,
-        user_id: Uuid,
     ) -> Result<Room> {
-        let mut storage = get_storage(ctx).await;
+        let user_id = require_user(ctx)?;
 
-        match storage.get_mut(&room_id) {
-            Some(room) => {
-                room.mark_chat_seen(user_id);
+        get_repository(ctx)
+            .update_with(room_id, |room| {
+                room.mark_chat_seen(user_id)?;
                 room.touch();
 
-                SimpleBroker::publish(room.get_room());
+                RoomChange::publish(room, |room| RoomDelta::user_updated(room, user_id));
                 Ok(room.get_room())
-            }
-            None => Err(Error::new("Room not found")),
-        }
+            })
+            .await
     }
 }
 
 pub struct SubscriptionRoot;
 
+/// Every subscription is for the room's members: subscribing needs a session
+/// token in `connection_init` and fails with `UNAUTHENTICATED`, `ROOM_NOT_FOUND`
+/// or `NOT_ROOM_MEMBER` otherwise. A stream ends once its subscriber is no longer
+/// in the room; `roomEvents` still delivers the event telling a kicked or banned
+/// user so before it does.
 #[Subscription]
 impl SubscriptionRoot {
-    async fn room(&self, room_id: EntityId) -> impl Stream<Item = Room> {
-        SimpleBroker::<Room>::subscribe().filter(move |event| {
-            let is_current_room = room_id == event.id;
+    async fn room(&self, ctx: &Context<'_>, room_id: EntityId) -> Result<impl Stream<Item = Room>> {
+        require_member(ctx, room_id).await?;
+        let stream = track_presence(ctx, room_id, SimpleBroker::<Room>::subscribe(room_id));
+        Ok(while_member(ctx, room_id, stream, |_, _| false))
+    }
 
-            async move { is_current_room }
-        })
+    /// Typed changes to the room, lighter than the full snapshots of `room`.
+    async fn room_changes(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+    ) -> Result<impl Stream<Item = RoomChange>> {
+        require_member(ctx, room_id).await?;
+        let stream = track_presence(ctx, room_id, SimpleBroker::<RoomChange>::subscribe(room_id));
+        Ok(while_member(ctx, room_id, stream, |_, _| false))
     }
 
-    async fn room_events(&self, room_id: EntityId) -> impl Stream<Item = RoomEvent> {
-        SimpleBroker::<RoomEvent>::subscribe().filter(move |event| {
-            let is_current_room = room_id == event.room_id;
-            async move { is_current_room }
-        })
+    async fn room_events(
+        &self,
+        ctx: &Context<'_>,
+        room_id: EntityId,
+    ) -> Result<impl Stream<Item = RoomEvent>> {
+        require_member(ctx, room_id).await?;
+        let stream = track_presence(ctx, room_id, SimpleBroker::<RoomEvent>::subscribe(room_id));
+        Ok(while_member(ctx, room_id, stream, |event, user_id| {
+            event.target_user_id == Some(user_id)
+        }))
     }
 
-    async fn room_chat(&self, room_id: Uuid) -> impl Stream<Item = ChatMessage> {
-        SimpleBroker::<ChatMessage>::subscribe()
-            .filter(move |msg| {
-                let same_room = msg.room_id == room_id;
-                async move { same_room }
-            })
+    async fn room_chat(
+        &self,
+        ctx: &Context<'_>,
+        room_id: Uuid,
+    ) -> Result<impl Stream<Item = ChatMessage>> {
+        require_member(ctx, room_id).await?;
+        let stream = track_presence(ctx, room_id, SimpleBroker::<ChatMessage>::subscribe(room_id));
+        Ok(while_member(ctx, room_id, stream, |_, _| false))
     }
 }
@@ -1,26 +1,159 @@
 use std::{
     any::{Any, TypeId},
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     marker::PhantomData,
     pin::Pin,
-    sync::Mutex,
-    task::{Context, Poll},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
 };
 
-use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
-use futures_util::{Stream, StreamExt};
-use once_cell::sync::Lazy;
+use futures_util::Stream;
+use once_cell::sync::{Lazy, OnceCell};
+use prometheus::IntCounterVec;
 use slab::Slab;
 
+use crate::{
+    configuration::{BrokerSettings, SlowConsumerPolicy},
+    types::EntityId,
+};
+
 static SUBSCRIBERS: Lazy<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> = Lazy::new(Default::default);
 
-struct Senders<T>(Slab<UnboundedSender<T>>);
+static CONFIG: OnceCell<BrokerConfig> = OnceCell::new();
+
+static DEFAULT_CONFIG: Lazy<BrokerConfig> = Lazy::new(|| BrokerConfig {
+    settings: BrokerSettings::default(),
+    metrics: None,
+});
+
+/// A message routed to the subscribers of one topic (a room).
+pub trait Topic {
+    /// Label for this kind of subscription in the broker metrics.
+    const SUBSCRIPTION: &'static str;
+
+    /// Whether a message carries the full state, making queued ones of the
+    /// same topic obsolete. Only such messages are coalesced.
+    const SNAPSHOT: bool = false;
+
+    fn topic(&self) -> EntityId;
+}
+
+/// Counters for messages a slow subscriber never received, labelled by
+/// `subscription`.
+#[derive(Clone)]
+pub struct BrokerMetrics {
+    pub dropped: IntCounterVec,
+    pub coalesced: IntCounterVec,
+    pub disconnected: IntCounterVec,
+}
+
+impl BrokerMetrics {
+    /// Count what an overflow cost a `subscription`.
+    fn record(&self, subscription: &str, overflow: &Overflow) {
+        match overflow {
+            Overflow::Dropped(count) => self.dropped.with_label_values(&[subscription]).inc_by(*count),
+            Overflow::Coalesced(count) => self.coalesced.with_label_values(&[subscription]).inc_by(*count),
+            Overflow::Disconnected => self.disconnected.with_label_values(&[subscription]).inc(),
+        }
+    }
+}
+
+struct BrokerConfig {
+    settings: BrokerSettings,
+    metrics: Option<BrokerMetrics>,
+}
+
+fn config() -> &'static BrokerConfig {
+    CONFIG.get().unwrap_or(&DEFAULT_CONFIG)
+}
+
+/// Set the queue size, slow-consumer policy and metrics. Call once at startup,
+/// before anything subscribes; later calls are ignored.
+pub fn configure(settings: BrokerSettings, metrics: BrokerMetrics) {
+    let _ = CONFIG.set(BrokerConfig {
+        settings,
+        metrics: Some(metrics),
+    });
+}
+
+/// The messages waiting for one subscriber.
+struct Queue<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+type SharedQueue<T> = Arc<Mutex<Queue<T>>>;
+
+/// What happened when a message was offered to a full queue.
+#[derive(Debug, PartialEq)]
+enum Overflow {
+    Dropped(u64),
+    Coalesced(u64),
+    Disconnected,
+}
+
+impl<T: Topic> Queue<T> {
+    /// Queue `msg`, applying `policy` when `capacity` is reached.
+    fn push(&mut self, msg: T, capacity: usize, policy: SlowConsumerPolicy) -> Option<Overflow> {
+        let overflow = if self.items.len() < capacity.max(1) {
+            None
+        } else {
+            match policy {
+                SlowConsumerPolicy::Coalesce if T::SNAPSHOT => {
+                    let coalesced = self.items.len() as u64;
+                    self.items.clear();
+                    Some(Overflow::Coalesced(coalesced))
+                }
+                SlowConsumerPolicy::DropOldest | SlowConsumerPolicy::Coalesce => {
+                    self.items.pop_front();
+                    Some(Overflow::Dropped(1))
+                }
+                SlowConsumerPolicy::Disconnect => {
+                    self.items.clear();
+                    self.closed = true;
+                    Some(Overflow::Disconnected)
+                }
+            }
+        };
+
+        if !self.closed {
+            self.items.push_back(msg);
+        }
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+
+        overflow
+    }
+}
 
-struct BrokerStream<T: Sync + Send + Clone + 'static>(usize, UnboundedReceiver<T>);
+/// Subscribers of one message type, grouped by topic.
+struct Senders<T>(HashMap<EntityId, Slab<SharedQueue<T>>>);
+
+impl<T> Senders<T> {
+    fn remove(&mut self, topic: EntityId, id: usize, queue: &SharedQueue<T>) {
+        if let Some(subscribers) = self.0.get_mut(&topic) {
+            // The slot may have been reused after a disconnect.
+            if subscribers.get(id).is_some_and(|q| Arc::ptr_eq(q, queue)) {
+                subscribers.remove(id);
+            }
+            if subscribers.is_empty() {
+                self.0.remove(&topic);
+            }
+        }
+    }
+}
+
+struct BrokerStream<T: Topic + Sync + Send + Clone + 'static> {
+    topic: EntityId,
+    id: usize,
+    queue: SharedQueue<T>,
+}
 
 fn with_senders<T, F, R>(f: F) -> R
 where
-    T: Sync + Send + Clone + 'static,
+    T: Topic + Sync + Send + Clone + 'static,
     F: FnOnce(&mut Senders<T>) -> R,
 {
     let mut map = SUBSCRIBERS.lock().unwrap();
@@ -30,39 +163,191 @@ where
     f(senders.downcast_mut::<Senders<T>>().unwrap())
 }
 
-impl<T: Sync + Send + Clone + 'static> Drop for BrokerStream<T> {
+impl<T: Topic + Sync + Send + Clone + 'static> Drop for BrokerStream<T> {
     fn drop(&mut self) {
-        with_senders::<T, _, _>(|senders| senders.0.remove(self.0));
+        with_senders::<T, _, _>(|senders| senders.remove(self.topic, self.id, &self.queue));
     }
 }
 
-impl<T: Sync + Send + Clone + 'static> Stream for BrokerStream<T> {
+impl<T: Topic + Sync + Send + Clone + 'static> Stream for BrokerStream<T> {
     type Item = T;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.1.poll_next_unpin(cx)
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut queue = self.queue.lock().unwrap();
+
+        if let Some(msg) = queue.items.pop_front() {
+            Poll::Ready(Some(msg))
+        } else if queue.closed {
+            Poll::Ready(None)
+        } else {
+            queue.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
     }
 }
 
 /// A simple broker based on memory
 pub struct SimpleBroker<T>(PhantomData<T>);
 
-impl<T: Sync + Send + Clone + 'static> SimpleBroker<T> {
-    /// Publish a message that all subscription streams can receive.
+impl<T: Topic + Sync + Send + Clone + 'static> SimpleBroker<T> {
+    /// Publish a message to the subscription streams of its topic.
+    ///
+    /// Only that topic's subscribers are visited, however many other topics
+    /// have subscribers. Each subscriber queues at most `queue_capacity`
+    /// messages; past that the configured [`SlowConsumerPolicy`] applies.
     pub fn publish(msg: T) {
+        let topic = msg.topic();
+        let config = config();
+        let (capacity, policy) = (config.settings.queue_capacity, config.settings.slow_consumer_policy);
+
         with_senders::<T, _, _>(|senders| {
-            for (_, sender) in senders.0.iter_mut() {
-                sender.start_send(msg.clone()).ok();
+            let Some(subscribers) = senders.0.get_mut(&topic) else {
+                return;
+            };
+
+            let mut disconnected = Vec::new();
+            for (id, queue) in subscribers.iter() {
+                let Some(overflow) = queue.lock().unwrap().push(msg.clone(), capacity, policy) else {
+                    continue;
+                };
+
+                if let Some(metrics) = &config.metrics {
+                    metrics.record(T::SUBSCRIPTION, &overflow);
+                }
+                if overflow == Overflow::Disconnected {
+                    disconnected.push(id);
+                }
+            }
+
+            for id in disconnected {
+                subscribers.remove(id);
+            }
+            if subscribers.is_empty() {
+                senders.0.remove(&topic);
             }
         });
     }
 
-    /// Subscribe to the message of the specified type and returns a `Stream`.
-    pub fn subscribe() -> impl Stream<Item = T> {
+    /// Subscribe to the messages of the specified type and topic and returns a `Stream`.
+    pub fn subscribe(topic: EntityId) -> impl Stream<Item = T> {
         with_senders::<T, _, _>(|senders| {
-            let (tx, rx) = mpsc::unbounded();
-            let id = senders.0.insert(tx);
-            BrokerStream(id, rx)
+            let queue = Arc::new(Mutex::new(Queue {
+                items: VecDeque::new(),
+                closed: false,
+                waker: None,
+            }));
+            let id = senders.0.entry(topic).or_default().insert(queue.clone());
+            BrokerStream { topic, id, queue }
         })
     }
+
+    /// Whether anyone is subscribed to `topic`, to skip building messages nobody receives.
+    pub fn has_subscribers(topic: EntityId) -> bool {
+        with_senders::<T, _, _>(|senders| senders.0.contains_key(&topic))
+    }
+
+    /// Number of topics with at least one subscriber.
+    pub fn topic_count() -> usize {
+        with_senders::<T, _, _>(|senders| senders.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::Opts;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Change(u32);
+
+    impl Topic for Change {
+        const SUBSCRIPTION: &'static str = "change";
+
+        fn topic(&self) -> EntityId {
+            EntityId::nil()
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Snapshot(u32);
+
+    impl Topic for Snapshot {
+        const SUBSCRIPTION: &'static str = "snapshot";
+        const SNAPSHOT: bool = true;
+
+        fn topic(&self) -> EntityId {
+            EntityId::nil()
+        }
+    }
+
+    /// A queue of capacity 2 after offering it `count` messages under `policy`,
+    /// with the overflow of each push.
+    fn fill<T: Topic>(
+        message: impl Fn(u32) -> T,
+        count: u32,
+        policy: SlowConsumerPolicy,
+    ) -> (Queue<T>, Vec<Option<Overflow>>) {
+        let mut queue = Queue {
+            items: VecDeque::new(),
+            closed: false,
+            waker: None,
+        };
+        let overflows = (1..=count).map(|n| queue.push(message(n), 2, policy)).collect();
+        (queue, overflows)
+    }
+
+    #[test]
+    fn drop_oldest_makes_room_one_message_at_a_time() {
+        let (queue, overflows) = fill(Snapshot, 4, SlowConsumerPolicy::DropOldest);
+
+        assert_eq!(queue.items, [Snapshot(3), Snapshot(4)]);
+        assert_eq!(overflows, [None, None, Some(Overflow::Dropped(1)), Some(Overflow::Dropped(1))]);
+        assert!(!queue.closed);
+    }
+
+    #[test]
+    fn coalesce_replaces_queued_snapshots_with_the_latest() {
+        let (queue, overflows) = fill(Snapshot, 3, SlowConsumerPolicy::Coalesce);
+
+        assert_eq!(queue.items, [Snapshot(3)]);
+        assert_eq!(overflows, [None, None, Some(Overflow::Coalesced(2))]);
+    }
+
+    #[test]
+    fn coalesce_drops_the_oldest_of_other_messages() {
+        let (queue, overflows) = fill(Change, 3, SlowConsumerPolicy::Coalesce);
+
+        assert_eq!(queue.items, [Change(2), Change(3)]);
+        assert_eq!(overflows, [None, None, Some(Overflow::Dropped(1))]);
+    }
+
+    #[test]
+    fn disconnect_closes_the_queue() {
+        let (queue, overflows) = fill(Change, 4, SlowConsumerPolicy::Disconnect);
+
+        assert!(queue.items.is_empty());
+        assert!(queue.closed);
+        assert_eq!(overflows[2], Some(Overflow::Disconnected));
+    }
+
+    #[test]
+    fn overflows_are_counted_per_subscription() {
+        let counter = |name: &str| IntCounterVec::new(Opts::new(name, name), &["subscription"]).unwrap();
+        let metrics = BrokerMetrics {
+            dropped: counter("dropped"),
+            coalesced: counter("coalesced"),
+            disconnected: counter("disconnected"),
+        };
+
+        metrics.record("change", &Overflow::Dropped(1));
+        metrics.record("change", &Overflow::Dropped(1));
+        metrics.record("room", &Overflow::Coalesced(3));
+        metrics.record("room", &Overflow::Disconnected);
+
+        assert_eq!(metrics.dropped.with_label_values(&["change"]).get(), 2);
+        assert_eq!(metrics.dropped.with_label_values(&["room"]).get(), 0);
+        assert_eq!(metrics.coalesced.with_label_values(&["room"]).get(), 3);
+        assert_eq!(metrics.disconnected.with_label_values(&["room"]).get(), 1);
+    }
 }
@@ -1,12 +1,13 @@
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
-use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::domain::room::Room;
+use crate::{persistence::RoomStore, repository::RoomRepository};
 
 pub type EntityId = Uuid;
 
 pub type Card = String;
 
-pub type Storage = Arc<Mutex<HashMap<EntityId, Room>>>;
+pub type Repository = Arc<dyn RoomRepository>;
+
+pub type Persistence = Arc<dyn RoomStore>;
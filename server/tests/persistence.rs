@@ -0,0 +1,142 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use planning_poker_server::{
+    domain::room::Room,
+    persistence::{AppendOnlyFileStore, RoomStore},
+    repository::{InMemoryRoomRepository, RoomRepository},
+    types::EntityId,
+};
+use uuid::Uuid;
+
+fn log_path() -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("room-store-{}", Uuid::new_v4()))
+        .join("rooms.jsonl")
+}
+
+fn named(room: &Room, name: &str) -> Room {
+    let mut room = room.clone();
+    room.name = Some(name.to_string());
+    room.touch();
+    room
+}
+
+fn load(path: &Path) -> Vec<Room> {
+    let store = AppendOnlyFileStore::open(path).unwrap();
+    store.load().unwrap()
+}
+
+#[test]
+fn writes_during_a_compaction_survive_it() {
+    let path = log_path();
+    let store = AppendOnlyFileStore::open(&path).unwrap();
+    let room = Room::new(Some("first".to_string()), vec![]);
+    let removed = Room::new(None, vec![]);
+    store.save(&room).unwrap();
+    store.save(&removed).unwrap();
+
+    store.rotate().unwrap();
+    // The snapshot below was copied before these landed.
+    let stale = vec![room.clone(), removed.clone()];
+    store.save(&named(&room, "second")).unwrap();
+    store.delete(removed.id).unwrap();
+    let added = Room::new(Some("added".to_string()), vec![]);
+    store.save(&added).unwrap();
+    store.compact(&stale).unwrap();
+    drop(store);
+
+    let mut rooms = load(&path);
+    rooms.sort_by_key(|room| room.name.clone());
+    let names: Vec<_> = rooms.iter().map(|room| room.name.as_deref()).collect();
+    assert_eq!(names, [Some("added"), Some("second")]);
+    assert!(!path.with_file_name("rooms.jsonl.tail").exists());
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn an_unfinished_compaction_is_replayed_on_load() {
+    let path = log_path();
+    let store = AppendOnlyFileStore::open(&path).unwrap();
+    let room = Room::new(Some("first".to_string()), vec![]);
+    store.save(&room).unwrap();
+
+    store.rotate().unwrap();
+    store.save(&named(&room, "second")).unwrap();
+    // Shut down before the compaction ran.
+    drop(store);
+
+    let rooms = load(&path);
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].name.as_deref(), Some("second"));
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn a_failed_rotation_is_reported_and_keeps_writing_to_the_log() {
+    let path = log_path();
+    let store = AppendOnlyFileStore::open(&path).unwrap();
+    let room = Room::new(Some("first".to_string()), vec![]);
+    store.save(&room).unwrap();
+
+    // Something in the way of the tail file.
+    let tail = path.with_file_name("rooms.jsonl.tail");
+    fs::create_dir(&tail).unwrap();
+    assert!(store.rotate().is_err());
+
+    store.save(&named(&room, "second")).unwrap();
+    drop(store);
+    fs::remove_dir(&tail).unwrap();
+
+    let rooms = load(&path);
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].name.as_deref(), Some("second"));
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+/// A store whose tail file can never be opened.
+#[derive(Default)]
+struct UnrotatableStore {
+    compactions: AtomicUsize,
+}
+
+impl RoomStore for UnrotatableStore {
+    fn load(&self) -> io::Result<Vec<Room>> {
+        Ok(Vec::new())
+    }
+
+    fn save(&self, _room: &Room) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn delete(&self, _room_id: EntityId) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn compact(&self, _rooms: &[Room]) -> io::Result<()> {
+        self.compactions.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        Err(io::Error::other("no room for a tail"))
+    }
+}
+
+#[tokio::test]
+async fn compaction_is_skipped_when_rotation_fails() {
+    let store = Arc::new(UnrotatableStore::default());
+    let repository = InMemoryRoomRepository::with_rooms(store.clone(), vec![Room::new(None, vec![])]);
+
+    assert!(repository.compact().await.is_err());
+    assert_eq!(store.compactions.load(Ordering::SeqCst), 0);
+}
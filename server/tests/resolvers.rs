@@ -0,0 +1,649 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_graphql::{Error, Request, Response, Result, Schema, Variables};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use planning_poker_server::{
+    auth::{AuthenticatedUser, SessionSigner},
+    domain::{deck::DeckPreset, error::RoomError, room::Room, room_event::RoomEvent, user::User},
+    persistence::MemoryStore,
+    presence::PresenceTracker,
+    repository::{InMemoryRoomRepository, RoomMutation, RoomRepository},
+    scheduler::RoomScheduler,
+    simple_broker::SimpleBroker,
+    schema::{MutationRoot, PokerPlanningSchema, QueryRoot, SubscriptionRoot},
+    types::{EntityId, Repository},
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Rooms in a plain map, remembering every room it was asked to persist.
+#[derive(Default)]
+struct FakeRoomRepository {
+    rooms: Mutex<HashMap<EntityId, Room>>,
+    saved: Mutex<Vec<Room>>,
+}
+
+impl FakeRoomRepository {
+    fn saved(&self) -> usize {
+        self.saved.lock().unwrap().len()
+    }
+
+    fn room(&self, room_id: EntityId) -> Room {
+        self.rooms.lock().unwrap()[&room_id].clone()
+    }
+
+    fn apply(&self, room_id: EntityId, mutation: RoomMutation<'_>, persist: bool) -> Result<()> {
+        let mut rooms = self.rooms.lock().unwrap();
+        let room = rooms
+            .get_mut(&room_id)
+            .ok_or_else(|| Error::from(RoomError::RoomNotFound))?;
+
+        let mut updated = room.clone();
+        mutation(&mut updated)?;
+        *room = updated;
+
+        if persist {
+            self.saved.lock().unwrap().push(room.clone());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RoomRepository for FakeRoomRepository {
+    async fn get(&self, room_id: EntityId) -> Option<Room> {
+        self.rooms.lock().unwrap().get(&room_id).cloned()
+    }
+
+    async fn insert(&self, room: Room) -> Result<()> {
+        let mut rooms = self.rooms.lock().unwrap();
+        if rooms.contains_key(&room.id) {
+            return Err(RoomError::RoomExists.into());
+        }
+
+        self.saved.lock().unwrap().push(room.clone());
+        rooms.insert(room.id, room);
+        Ok(())
+    }
+
+    async fn remove(&self, room_id: EntityId) -> Option<Room> {
+        self.rooms.lock().unwrap().remove(&room_id)
+    }
+
+    async fn update(&self, room_id: EntityId, mutation: RoomMutation<'_>) -> Result<()> {
+        self.apply(room_id, mutation, true)
+    }
+
+    async fn update_transient(&self, room_id: EntityId, mutation: RoomMutation<'_>) -> Result<()> {
+        self.apply(room_id, mutation, false)
+    }
+
+    async fn for_each(&self, visitor: &mut (dyn for<'r> FnMut(&'r Room) + Send)) {
+        for room in self.rooms.lock().unwrap().values() {
+            visitor(room);
+        }
+    }
+
+    async fn all(&self) -> Vec<Room> {
+        self.rooms.lock().unwrap().values().cloned().collect()
+    }
+
+    async fn find_by_user(&self, user_id: EntityId) -> Vec<Room> {
+        self.rooms
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|room| room.is_user_exist(user_id))
+            .cloned()
+            .collect()
+    }
+
+    async fn count(&self) -> usize {
+        self.rooms.lock().unwrap().len()
+    }
+}
+
+fn schema(repository: Repository) -> PokerPlanningSchema {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(repository.clone())
+        .data(RoomScheduler::new(repository.clone()))
+        .data(PresenceTracker::new(repository, None))
+        .data(SessionSigner::new("test-secret"))
+        .finish()
+}
+
+fn new_room() -> Room {
+    Room::new_with_id(
+        None,
+        Some("Sprint 42".to_string()),
+        ["1", "2", "3", "5", "8"].map(String::from).to_vec(),
+    )
+}
+
+async fn execute(schema: &PokerPlanningSchema, user_id: Option<EntityId>, query: String) -> Response {
+    let mut request = Request::new(query);
+    if let Some(user_id) = user_id {
+        request = request.data(AuthenticatedUser(user_id));
+    }
+    schema.execute(request).await
+}
+
+/// The first response of a subscription to the room's chat.
+async fn first_chat_message(schema: &PokerPlanningSchema, user_id: Option<EntityId>, room_id: EntityId) -> Response {
+    let mut request = Request::new(format!(r#"subscription {{ roomChat(roomId: "{room_id}") {{ content }} }}"#));
+    if let Some(user_id) = user_id {
+        request = request.data(AuthenticatedUser(user_id));
+    }
+    schema.execute_stream(request).next().await.unwrap()
+}
+
+/// Wait for presence updates, which land in background tasks, to settle.
+async fn eventually(repository: &FakeRoomRepository, room_id: EntityId, settled: impl Fn(&Room) -> bool) {
+    for _ in 0..200 {
+        if settled(&repository.room(room_id)) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("room {room_id} never settled");
+}
+
+fn is_online(room: &Room, user_id: EntityId) -> bool {
+    room.users.iter().any(|user| user.id == user_id && user.online)
+}
+
+fn data(response: Response) -> Value {
+    assert!(response.errors.is_empty(), "unexpected errors: {:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+fn error_code(response: &Response) -> Option<String> {
+    let response = serde_json::to_value(response).unwrap();
+    response["errors"][0]["extensions"]["code"]
+        .as_str()
+        .map(String::from)
+}
+
+async fn join(schema: &PokerPlanningSchema, room_id: EntityId, user_id: EntityId, owner: bool) -> Response {
+    let owner_arg = if owner {
+        format!(r#", roomOwnerId: "{user_id}""#)
+    } else {
+        String::new()
+    };
+    let query = format!(
+        r#"mutation {{ joinRoom(roomId: "{room_id}", user: {{ username: "user", roomName: "Renamed" }}{owner_arg}) {{ name roomOwnerId users {{ id }} }} }}"#
+    );
+    execute(schema, Some(user_id), query).await
+}
+
+async fn pick_card(schema: &PokerPlanningSchema, room_id: EntityId, user_id: EntityId, card: &str) -> Response {
+    let query = format!(
+        r#"mutation {{ pickCard(roomId: "{room_id}", card: "{card}") {{ isGameOver game {{ table {{ userId card }} }} }} }}"#
+    );
+    execute(schema, Some(user_id), query).await
+}
+
+async fn show_cards(schema: &PokerPlanningSchema, room_id: EntityId, user_id: EntityId) -> Response {
+    let query = format!(
+        r#"mutation {{ showCards(roomId: "{room_id}") {{ isGameOver game {{ table {{ userId card }} }} }} }}"#
+    );
+    execute(schema, Some(user_id), query).await
+}
+
+#[tokio::test]
+async fn join_room_adds_the_user_and_lets_the_creator_claim_it() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let (owner, guest) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let joined = data(join(&schema, room_id, owner, true).await);
+    assert_eq!(joined["joinRoom"]["roomOwnerId"], json!(owner));
+    assert_eq!(joined["joinRoom"]["users"], json!([{ "id": owner }]));
+
+    // A later joiner can't take the room over.
+    data(join(&schema, room_id, guest, true).await);
+    let room = repository.room(room_id);
+    assert_eq!(room.room_owner_id, Some(owner));
+    assert_eq!(room.users.len(), 2);
+    assert_eq!(repository.saved(), 3);
+}
+
+#[tokio::test]
+async fn join_room_only_lets_the_claiming_creator_name_the_room() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+
+    let joined = data(join(&schema, room_id, Uuid::new_v4(), false).await);
+    assert_eq!(joined["joinRoom"]["name"], json!("Sprint 42"));
+
+    let claimed = data(join(&schema, room_id, Uuid::new_v4(), true).await);
+    assert_eq!(claimed["joinRoom"]["name"], json!("Renamed"));
+}
+
+#[tokio::test]
+async fn join_room_requires_a_session() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+
+    let query = format!(r#"mutation {{ joinRoom(roomId: "{room_id}", user: {{ username: "user" }}) {{ id }} }}"#);
+    let response = execute(&schema, None, query).await;
+
+    assert_eq!(error_code(&response).as_deref(), Some("UNAUTHENTICATED"));
+    assert!(repository.room(room_id).users.is_empty());
+}
+
+#[tokio::test]
+async fn join_room_fails_for_a_missing_room() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository);
+
+    let response = join(&schema, Uuid::new_v4(), Uuid::new_v4(), false).await;
+
+    assert_eq!(error_code(&response).as_deref(), Some("ROOM_NOT_FOUND"));
+}
+
+#[tokio::test]
+async fn pick_card_hides_votes_until_the_reveal() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let (owner, guest) = (Uuid::new_v4(), Uuid::new_v4());
+    data(join(&schema, room_id, owner, true).await);
+    data(join(&schema, room_id, guest, false).await);
+
+    let voted = data(pick_card(&schema, room_id, guest, "5").await);
+
+    assert_eq!(voted["pickCard"]["isGameOver"], json!(false));
+    assert_eq!(
+        voted["pickCard"]["game"]["table"],
+        json!([{ "userId": guest, "card": null }])
+    );
+    assert_eq!(repository.room(room_id).game.table[0].card.as_deref(), Some("5"));
+}
+
+#[tokio::test]
+async fn pick_card_rejects_cards_outside_the_deck_without_saving() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let user_id = Uuid::new_v4();
+    data(join(&schema, room_id, user_id, true).await);
+    let saved = repository.saved();
+
+    let response = pick_card(&schema, room_id, user_id, "13").await;
+
+    assert!(error_code(&response).is_some());
+    assert!(repository.room(room_id).game.table.is_empty());
+    assert_eq!(repository.saved(), saved);
+}
+
+#[tokio::test]
+async fn pick_card_requires_membership() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+
+    let response = pick_card(&schema, room_id, Uuid::new_v4(), "5").await;
+
+    assert!(error_code(&response).is_some());
+    assert!(repository.room(room_id).game.table.is_empty());
+}
+
+#[tokio::test]
+async fn show_cards_reveals_every_vote() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let (owner, guest) = (Uuid::new_v4(), Uuid::new_v4());
+    data(join(&schema, room_id, owner, true).await);
+    data(join(&schema, room_id, guest, false).await);
+    data(pick_card(&schema, room_id, owner, "3").await);
+    data(pick_card(&schema, room_id, guest, "8").await);
+
+    let revealed = data(show_cards(&schema, room_id, owner).await);
+
+    assert_eq!(revealed["showCards"]["isGameOver"], json!(true));
+    assert_eq!(
+        revealed["showCards"]["game"]["table"],
+        json!([{ "userId": owner, "card": "3" }, { "userId": guest, "card": "8" }])
+    );
+}
+
+#[tokio::test]
+async fn show_cards_is_for_the_owner_only() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let (owner, guest) = (Uuid::new_v4(), Uuid::new_v4());
+    data(join(&schema, room_id, owner, true).await);
+    data(join(&schema, room_id, guest, false).await);
+
+    let response = show_cards(&schema, room_id, guest).await;
+
+    assert_eq!(error_code(&response).as_deref(), Some("FORBIDDEN"));
+    assert!(!repository.room(room_id).is_game_over);
+}
+
+#[tokio::test]
+async fn a_failed_update_leaves_the_room_untouched() {
+    let repository: Repository = Arc::new(InMemoryRoomRepository::with_rooms(Arc::new(MemoryStore), vec![]));
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let owner = Uuid::new_v4();
+    data(join(&schema, room_id, owner, true).await);
+    let before = repository.get(room_id).await.unwrap();
+
+    // The countdown length is valid, the auto-reset delay isn't.
+    let query = format!(
+        r#"mutation {{ updateRoomSettings(input: {{ roomId: "{room_id}", countdownSeconds: 7, autoReveal: true, autoResetSeconds: -1 }}) {{ id }} }}"#
+    );
+    let response = execute(&schema, Some(owner), query).await;
+
+    assert!(error_code(&response).is_some());
+    let after = repository.get(room_id).await.unwrap();
+    assert_eq!(after.countdown_seconds, before.countdown_seconds);
+    assert_eq!(after.auto_reveal, before.auto_reveal);
+    assert_eq!(after.version, before.version);
+}
+
+#[tokio::test]
+async fn subscriptions_are_for_members_only() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+
+    let anonymous = first_chat_message(&schema, None, room_id).await;
+    assert_eq!(error_code(&anonymous).as_deref(), Some("UNAUTHENTICATED"));
+
+    let stranger = first_chat_message(&schema, Some(Uuid::new_v4()), room_id).await;
+    assert_eq!(error_code(&stranger).as_deref(), Some("NOT_ROOM_MEMBER"));
+
+    let missing = first_chat_message(&schema, Some(Uuid::new_v4()), Uuid::new_v4()).await;
+    assert_eq!(error_code(&missing).as_deref(), Some("ROOM_NOT_FOUND"));
+}
+
+#[tokio::test]
+async fn members_receive_room_chat() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let user_id = Uuid::new_v4();
+    data(join(&schema, room_id, user_id, true).await);
+
+    let subscription = {
+        let schema = schema.clone();
+        tokio::spawn(async move { first_chat_message(&schema, Some(user_id), room_id).await })
+    };
+    // Keep sending until the subscription is listening.
+    while !subscription.is_finished() {
+        let query = format!(
+            r#"mutation {{ sendChatMessage(input: {{ roomId: "{room_id}", content: "hi", contentType: "text" }}) {{ id }} }}"#
+        );
+        data(execute(&schema, Some(user_id), query).await);
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let message = data(subscription.await.unwrap());
+    assert_eq!(message["roomChat"]["content"], json!("hi"));
+}
+
+#[tokio::test]
+async fn a_kicked_subscriber_stops_receiving_the_room() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let (owner, guest) = (Uuid::new_v4(), Uuid::new_v4());
+    data(join(&schema, room_id, owner, true).await);
+    data(join(&schema, room_id, guest, false).await);
+
+    let subscribe = |query: String| {
+        let schema = schema.clone();
+        let request = Request::new(query).data(AuthenticatedUser(guest));
+        tokio::spawn(async move { schema.execute_stream(request).collect::<Vec<_>>().await })
+    };
+    let rooms = subscribe(format!(r#"subscription {{ room(roomId: "{room_id}") {{ users {{ id }} }} }}"#));
+    let events = subscribe(format!(
+        r#"subscription {{ roomEvents(roomId: "{room_id}") {{ eventType targetUserId }} }}"#
+    ));
+    while !(SimpleBroker::<Room>::has_subscribers(room_id) && SimpleBroker::<RoomEvent>::has_subscribers(room_id)) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let kick = format!(r#"mutation {{ kickUser(roomId: "{room_id}", targetUserId: "{guest}") {{ id }} }}"#);
+    data(execute(&schema, Some(owner), kick).await);
+    // The stream ends, having sent nothing from after the kick.
+    let rooms = tokio::time::timeout(Duration::from_secs(5), rooms).await.unwrap().unwrap();
+    for room in rooms.into_iter().map(data) {
+        let users = room["room"]["users"].as_array().unwrap().clone();
+        assert!(users.contains(&json!({ "id": guest })), "a kicked user was sent {room}");
+    }
+
+    // The event telling them so still arrives, then nothing more.
+    let rename = format!(r#"mutation {{ renameRoom(roomId: "{room_id}", name: "Later") {{ id }} }}"#);
+    data(execute(&schema, Some(owner), rename).await);
+    let events = tokio::time::timeout(Duration::from_secs(5), events).await.unwrap().unwrap();
+    let last = events.into_iter().map(data).last();
+    assert_eq!(
+        last,
+        Some(json!({ "roomEvents": { "eventType": "USER_KICKED", "targetUserId": guest } }))
+    );
+}
+
+#[tokio::test]
+async fn room_sync_only_sends_a_room_that_moved_on() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let user_id = Uuid::new_v4();
+    data(join(&schema, room_id, user_id, true).await);
+    let version = repository.room(room_id).version;
+    let sync = |since: u64| {
+        format!(r#"{{ roomSync(roomId: "{room_id}", sinceVersion: {since}) {{ version room {{ id }} }} }}"#)
+    };
+
+    let unchanged = data(execute(&schema, Some(user_id), sync(version)).await);
+    assert_eq!(unchanged["roomSync"], json!({ "version": version, "room": null }));
+
+    let behind = data(execute(&schema, Some(user_id), sync(version - 1)).await);
+    assert_eq!(behind["roomSync"]["room"], json!({ "id": room_id }));
+
+    // A server restarted without its unpersisted changes can be behind the client.
+    let ahead = data(execute(&schema, Some(user_id), sync(version + 5)).await);
+    assert_eq!(ahead["roomSync"]["room"], json!({ "id": room_id }));
+}
+
+#[tokio::test]
+async fn room_sync_explains_why_there_is_no_room() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let sync = |room_id: EntityId| format!(r#"{{ roomSync(roomId: "{room_id}", sinceVersion: 0) {{ version }} }}"#);
+
+    let stranger = execute(&schema, Some(Uuid::new_v4()), sync(room_id)).await;
+    assert_eq!(error_code(&stranger).as_deref(), Some("NOT_ROOM_MEMBER"));
+
+    let missing = execute(&schema, Some(Uuid::new_v4()), sync(Uuid::new_v4())).await;
+    assert_eq!(error_code(&missing).as_deref(), Some("ROOM_NOT_FOUND"));
+}
+
+#[tokio::test]
+async fn room_preview_tells_strangers_whether_a_room_exists() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let preview = |room_id: EntityId| {
+        format!(r#"{{ roomPreview(roomId: "{room_id}") {{ exists name hasPasscode }} }}"#)
+    };
+
+    let existing = data(execute(&schema, None, preview(room_id)).await);
+    assert_eq!(
+        existing["roomPreview"],
+        json!({ "exists": true, "name": "Sprint 42", "hasPasscode": false })
+    );
+
+    let missing = data(execute(&schema, None, preview(Uuid::new_v4())).await);
+    assert_eq!(
+        missing["roomPreview"],
+        json!({ "exists": false, "name": null, "hasPasscode": false })
+    );
+}
+
+#[tokio::test]
+async fn presence_is_neither_persisted_nor_activity() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let mut room = new_room();
+    let user = User::new("Alice".to_string());
+    let (room_id, user_id) = (room.id, user.id);
+    room.users.push(user);
+    let (version, last_active) = (room.version, room.last_active);
+    repository.insert(room).await.unwrap();
+    let presence = PresenceTracker::new(repository.clone(), None);
+
+    let guard = presence.connect(room_id, user_id);
+    eventually(&repository, room_id, |room| is_online(room, user_id)).await;
+    drop(guard);
+    eventually(&repository, room_id, |room| !is_online(room, user_id)).await;
+
+    let room = repository.room(room_id);
+    assert_eq!(room.version, version + 2);
+    assert_eq!(room.last_active, last_active);
+    assert_eq!(repository.saved(), 1);
+}
+
+#[tokio::test]
+async fn restored_users_who_never_reconnect_are_removed() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let mut room = new_room();
+    let (gone, back) = (User::new("Gone".to_string()), User::new("Back".to_string()));
+    let (room_id, gone_id, back_id) = (room.id, gone.id, back.id);
+    room.users.extend([gone, back]);
+    repository.insert(room).await.unwrap();
+    let presence = PresenceTracker::new(repository.clone(), Some(Duration::from_millis(50)));
+
+    presence.expect_reconnects().await;
+    let _guard = presence.connect(room_id, back_id);
+
+    eventually(&repository, room_id, |room| !room.is_user_exist(gone_id)).await;
+    let room = repository.room(room_id);
+    assert!(room.is_user_exist(back_id));
+    assert!(is_online(&room, back_id));
+}
+
+#[tokio::test(start_paused = true)]
+async fn changing_auto_reset_during_a_reveal_reschedules_it() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let owner = Uuid::new_v4();
+    data(join(&schema, room_id, owner, true).await);
+    let auto_reset = |seconds: &str| {
+        format!(
+            r#"mutation {{ updateRoomSettings(input: {{ roomId: "{room_id}", autoResetSeconds: {seconds} }}) {{ autoResetSeconds }} }}"#
+        )
+    };
+
+    // Turned on after the reveal: the round still resets.
+    data(show_cards(&schema, room_id, owner).await);
+    data(execute(&schema, Some(owner), auto_reset("5")).await);
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    assert!(!repository.room(room_id).is_game_over);
+
+    // Turned off while waiting: it doesn't.
+    data(show_cards(&schema, room_id, owner).await);
+    data(execute(&schema, Some(owner), auto_reset("null")).await);
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    assert!(repository.room(room_id).is_game_over);
+}
+
+#[tokio::test]
+async fn create_room_never_replaces_an_existing_room() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let room = new_room();
+    let room_id = room.id;
+    repository.insert(room).await.unwrap();
+    let owner = Uuid::new_v4();
+    data(join(&schema, room_id, owner, true).await);
+    let saved = repository.saved();
+
+    let query = format!(r#"mutation {{ createRoom(roomId: "{room_id}", cards: ["1"]) {{ id }} }}"#);
+    let response = execute(&schema, Some(Uuid::new_v4()), query).await;
+    assert_eq!(error_code(&response).as_deref(), Some("ROOM_EXISTS"));
+
+    let room = repository.room(room_id);
+    assert_eq!(room.room_owner_id, Some(owner));
+    assert_eq!(room.users.len(), 1);
+    assert_eq!(repository.saved(), saved);
+}
+
+#[tokio::test]
+async fn create_room_without_cards_gets_the_default_deck() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+
+    // As the client's home page sends it.
+    let request = Request::new(
+        "mutation CreateRoom($roomId: UUID, $name: String, $cards: [String!]) { createRoom(roomId: $roomId, name: $name, cards: $cards) { id deck { preset cards } } }",
+    )
+    .variables(Variables::from_json(json!({ "cards": [] })));
+    let created = data(schema.execute(request).await);
+
+    let deck = &created["createRoom"]["deck"];
+    assert_eq!(deck["preset"], json!("MODIFIED_FIBONACCI"));
+    assert_eq!(deck["cards"].as_array().unwrap().len(), DeckPreset::DEFAULT.cards().len());
+}
+
+#[tokio::test]
+async fn special_cards_cannot_have_a_value() {
+    let repository = Arc::new(FakeRoomRepository::default());
+    let schema = schema(repository.clone());
+    let create = |card: &str| {
+        format!(r#"mutation {{ createRoom(cardDetails: [{{ label: "1", value: 1 }}, {card}]) {{ id }} }}"#)
+    };
+
+    let valued = execute(&schema, None, create(r#"{ label: "☕", special: BREAK, value: 0 }"#)).await;
+    assert_eq!(error_code(&valued).as_deref(), Some("INVALID_DECK"));
+    assert_eq!(repository.saved(), 0);
+
+    data(execute(&schema, None, create(r#"{ label: "☕", special: BREAK }"#)).await);
+}